@@ -0,0 +1,61 @@
+//! Neighbor and degree queries on synthetic scale-free-ish networks of
+//! increasing size; with adjacency lists their cost should track the queried
+//! node's degree, not the edge count.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use mcl1_regulator::analysis::interaction_network::{InteractionNetwork, ProteinInteraction};
+
+/// Random network with `edges` edges over `edges / 5` nodes, seeded so every
+/// run benchmarks the same graph.
+fn random_network(edges: usize) -> InteractionNetwork {
+    let nodes = (edges / 5).max(2);
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut network = InteractionNetwork::new();
+    for _ in 0..edges {
+        network.add_interaction(ProteinInteraction::new(
+            &format!("P{}", rng.gen_range(0..nodes)),
+            &format!("P{}", rng.gen_range(0..nodes)),
+            "binding",
+            rng.gen(),
+        ));
+    }
+    network
+}
+
+fn neighbor_queries(c: &mut Criterion) {
+    let mut group = c.benchmark_group("get_neighbors");
+    for edges in [5_000, 50_000, 500_000] {
+        let network = random_network(edges);
+        group.bench_with_input(BenchmarkId::from_parameter(edges), &network, |b, network| {
+            b.iter(|| black_box(network.get_neighbors(black_box("P1"))).len())
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("get_degree");
+    for edges in [5_000, 50_000, 500_000] {
+        let network = random_network(edges);
+        group.bench_with_input(BenchmarkId::from_parameter(edges), &network, |b, network| {
+            b.iter(|| network.get_degree(black_box("P1")))
+        });
+    }
+    group.finish();
+}
+
+fn subnetwork_extraction(c: &mut Criterion) {
+    let mut group = c.benchmark_group("get_subnetwork");
+    group.sample_size(10);
+    for edges in [5_000, 50_000] {
+        let network = random_network(edges);
+        group.bench_with_input(BenchmarkId::from_parameter(edges), &network, |b, network| {
+            b.iter(|| network.get_subnetwork(black_box(&["P1"])).edge_count())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, neighbor_queries, subnetwork_extraction);
+criterion_main!(benches);
@@ -0,0 +1,543 @@
+//! Pairwise and progressive multiple sequence alignment
+//!
+//! Pairwise alignment uses Gotoh's affine-gap form of Needleman-Wunsch
+//! (global) and Smith-Waterman (local). Multiple alignments are built
+//! progressively along a UPGMA guide tree of pairwise identity distances,
+//! and per-column conservation maps back onto `Domain` ranges.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::error::{MCL1Error, Result};
+use crate::models::protein::Protein;
+
+const MATRIX_ORDER: &str = "ARNDCQEGHILKMFPSTWYV";
+const GAP: char = '-';
+
+#[rustfmt::skip]
+const BLOSUM62_SCORES: [[i32; 20]; 20] = [
+    //A   R   N   D   C   Q   E   G   H   I   L   K   M   F   P   S   T   W   Y   V
+    [ 4, -1, -2, -2,  0, -1, -1,  0, -2, -1, -1, -1, -1, -2, -1,  1,  0, -3, -2,  0], // A
+    [-1,  5,  0, -2, -3,  1,  0, -2,  0, -3, -2,  2, -1, -3, -2, -1, -1, -3, -2, -3], // R
+    [-2,  0,  6,  1, -3,  0,  0,  0,  1, -3, -3,  0, -2, -3, -2,  1,  0, -4, -2, -3], // N
+    [-2, -2,  1,  6, -3,  0,  2, -1, -1, -3, -4, -1, -3, -3, -1,  0, -1, -4, -3, -3], // D
+    [ 0, -3, -3, -3,  9, -3, -4, -3, -3, -1, -1, -3, -1, -2, -3, -1, -1, -2, -2, -1], // C
+    [-1,  1,  0,  0, -3,  5,  2, -2,  0, -3, -2,  1,  0, -3, -1,  0, -1, -2, -1, -2], // Q
+    [-1,  0,  0,  2, -4,  2,  5, -2,  0, -3, -3,  1, -2, -3, -1,  0, -1, -3, -2, -2], // E
+    [ 0, -2,  0, -1, -3, -2, -2,  6, -2, -4, -4, -2, -3, -3, -2,  0, -2, -2, -3, -3], // G
+    [-2,  0,  1, -1, -3,  0,  0, -2,  8, -3, -3, -1, -2, -1, -2, -1, -2, -2,  2, -3], // H
+    [-1, -3, -3, -3, -1, -3, -3, -4, -3,  4,  2, -3,  1,  0, -3, -2, -1, -3, -1,  3], // I
+    [-1, -2, -3, -4, -1, -2, -3, -4, -3,  2,  4, -2,  2,  0, -3, -2, -1, -2, -1,  1], // L
+    [-1,  2,  0, -1, -3,  1,  1, -2, -1, -3, -2,  5, -1, -3, -1,  0, -1, -3, -2, -2], // K
+    [-1, -1, -2, -3, -1,  0, -2, -3, -2,  1,  2, -1,  5,  0, -2, -1, -1, -1, -1,  1], // M
+    [-2, -3, -3, -3, -2, -3, -3, -3, -1,  0,  0, -3,  0,  6, -4, -2, -2,  1,  3, -1], // F
+    [-1, -2, -2, -1, -3, -1, -1, -2, -2, -3, -3, -1, -2, -4,  7, -1, -1, -4, -3, -2], // P
+    [ 1, -1,  1,  0, -1,  0,  0,  0, -1, -2, -2,  0, -1, -2, -1,  4,  1, -3, -2, -2], // S
+    [ 0, -1,  0, -1, -1, -1, -1, -2, -2, -1, -1, -1, -1, -2, -1,  1,  5, -2, -2,  0], // T
+    [-3, -3, -4, -4, -2, -2, -3, -2, -2, -3, -2, -3, -1,  1, -4, -3, -2, 11,  2, -3], // W
+    [-2, -2, -2, -3, -2, -1, -2, -3,  2, -1, -1, -2, -1,  3, -3, -2, -2,  2,  7, -1], // Y
+    [ 0, -3, -3, -3, -1, -2, -2, -3, -3,  3,  1, -2,  1, -1, -2, -2,  0, -3, -1,  4], // V
+];
+
+#[rustfmt::skip]
+const PAM250_SCORES: [[i32; 20]; 20] = [
+    //A   R   N   D   C   Q   E   G   H   I   L   K   M   F   P   S   T   W   Y   V
+    [ 2, -2,  0,  0, -2,  0,  0,  1, -1, -1, -2, -1, -1, -3,  1,  1,  1, -6, -3,  0], // A
+    [-2,  6,  0, -1, -4,  1, -1, -3,  2, -2, -3,  3,  0, -4,  0,  0, -1,  2, -4, -2], // R
+    [ 0,  0,  2,  2, -4,  1,  1,  0,  2, -2, -3,  1, -2, -3,  0,  1,  0, -4, -2, -2], // N
+    [ 0, -1,  2,  4, -5,  2,  3,  1,  1, -2, -4,  0, -3, -6, -1,  0,  0, -7, -4, -2], // D
+    [-2, -4, -4, -5, 12, -5, -5, -3, -3, -2, -6, -5, -5, -4, -3,  0, -2, -8,  0, -2], // C
+    [ 0,  1,  1,  2, -5,  4,  2, -1,  3, -2, -2,  1, -1, -5,  0, -1, -1, -5, -4, -2], // Q
+    [ 0, -1,  1,  3, -5,  2,  4,  0,  1, -2, -3,  0, -2, -5, -1,  0,  0, -7, -4, -2], // E
+    [ 1, -3,  0,  1, -3, -1,  0,  5, -2, -3, -4, -2, -3, -5,  0,  1,  0, -7, -5, -1], // G
+    [-1,  2,  2,  1, -3,  3,  1, -2,  6, -2, -2,  0, -2, -2,  0, -1, -1, -3,  0, -2], // H
+    [-1, -2, -2, -2, -2, -2, -2, -3, -2,  5,  2, -2,  2,  1, -2, -1,  0, -5, -1,  4], // I
+    [-2, -3, -3, -4, -6, -2, -3, -4, -2,  2,  6, -3,  4,  2, -3, -3, -2, -2, -1,  2], // L
+    [-1,  3,  1,  0, -5,  1,  0, -2,  0, -2, -3,  5,  0, -5, -1,  0,  0, -3, -4, -2], // K
+    [-1,  0, -2, -3, -5, -1, -2, -3, -2,  2,  4,  0,  6,  0, -2, -2, -1, -4, -2,  2], // M
+    [-3, -4, -3, -6, -4, -5, -5, -5, -2,  1,  2, -5,  0,  9, -5, -3, -3,  0,  7, -1], // F
+    [ 1,  0,  0, -1, -3,  0, -1,  0,  0, -2, -3, -1, -2, -5,  6,  1,  0, -6, -5, -1], // P
+    [ 1,  0,  1,  0,  0, -1,  0,  1, -1, -1, -3,  0, -2, -3,  1,  2,  1, -2, -3, -1], // S
+    [ 1, -1,  0,  0, -2, -1,  0,  0, -1,  0, -2,  0, -1, -3,  0,  1,  3, -5, -3,  0], // T
+    [-6,  2, -4, -7, -8, -5, -7, -7, -3, -5, -2, -3, -4,  0, -6, -2, -5, 17,  0, -6], // W
+    [-3, -4, -2, -4,  0, -4, -4, -5,  0, -1, -1, -4, -2,  7, -5, -3, -3,  0, 10, -2], // Y
+    [ 0, -2, -2, -2, -2, -2, -2, -1, -2,  4,  2, -2,  2, -1, -1, -1,  0, -6, -2,  4], // V
+];
+
+/// Amino acid substitution scores.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SubstitutionMatrix {
+    pub name: &'static str,
+    scores: &'static [[i32; 20]; 20],
+    /// Score for any pair involving a non-standard residue.
+    pub unknown: i32,
+}
+
+impl SubstitutionMatrix {
+    pub const BLOSUM62: SubstitutionMatrix = SubstitutionMatrix {
+        name: "BLOSUM62",
+        scores: &BLOSUM62_SCORES,
+        unknown: -1,
+    };
+
+    pub const PAM250: SubstitutionMatrix = SubstitutionMatrix {
+        name: "PAM250",
+        scores: &PAM250_SCORES,
+        unknown: -1,
+    };
+
+    pub fn score(&self, a: char, b: char) -> i32 {
+        let index = |residue: char| MATRIX_ORDER.find(residue.to_ascii_uppercase());
+        match (index(a), index(b)) {
+            (Some(a), Some(b)) => self.scores[a][b],
+            _ => self.unknown,
+        }
+    }
+}
+
+impl Default for SubstitutionMatrix {
+    fn default() -> Self {
+        SubstitutionMatrix::BLOSUM62
+    }
+}
+
+/// Affine gap costs: a gap of length `k` costs `open + (k - 1) * extend`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GapPenalty {
+    pub open: f64,
+    pub extend: f64,
+}
+
+impl Default for GapPenalty {
+    fn default() -> Self {
+        Self { open: 10.0, extend: 0.5 }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AlignmentMode {
+    /// Needleman-Wunsch: align both sequences end to end.
+    Global,
+    /// Smith-Waterman: best-scoring pair of subsequences.
+    Local,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PairwiseAlignment {
+    pub mode: AlignmentMode,
+    pub score: f64,
+    /// Aligned rows with `-` for gaps.
+    pub aligned_a: String,
+    pub aligned_b: String,
+    /// 0-based offsets of the first aligned residue in each input.
+    pub start_a: usize,
+    pub start_b: usize,
+}
+
+impl PairwiseAlignment {
+    pub fn len(&self) -> usize {
+        self.aligned_a.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.aligned_a.is_empty()
+    }
+
+    /// Fraction of alignment columns with identical residues.
+    pub fn identity(&self) -> f64 {
+        if self.is_empty() {
+            return 0.0;
+        }
+        let identical = self
+            .aligned_a
+            .chars()
+            .zip(self.aligned_b.chars())
+            .filter(|(a, b)| a == b && *a != GAP)
+            .count();
+        identical as f64 / self.len() as f64
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum State {
+    Match,
+    GapInB,
+    GapInA,
+    Start,
+}
+
+/// Column pairing produced by the dynamic programme: indices into each input.
+type Column = (Option<usize>, Option<usize>);
+
+/// Aligned rows of a partial multiple alignment.
+type Profile = Vec<Vec<char>>;
+
+struct DpResult {
+    score: f64,
+    columns: Vec<Column>,
+}
+
+/// Gotoh dynamic programme over `n` x `m` positions scored by `score`.
+fn gotoh<F>(n: usize, m: usize, score: F, gap: &GapPenalty, mode: AlignmentMode) -> DpResult
+where
+    F: Fn(usize, usize) -> f64,
+{
+    let local = mode == AlignmentMode::Local;
+    let width = m + 1;
+    let cell = |i: usize, j: usize| i * width + j;
+    let size = (n + 1) * width;
+
+    let mut best_match = vec![f64::NEG_INFINITY; size];
+    let mut best_gap_b = vec![f64::NEG_INFINITY; size];
+    let mut best_gap_a = vec![f64::NEG_INFINITY; size];
+    let mut from_match = vec![State::Start; size];
+    let mut from_gap_b = vec![State::Start; size];
+    let mut from_gap_a = vec![State::Start; size];
+
+    best_match[0] = 0.0;
+    if !local {
+        for i in 1..=n {
+            best_gap_b[cell(i, 0)] = -(gap.open + (i - 1) as f64 * gap.extend);
+            from_gap_b[cell(i, 0)] = if i == 1 { State::Match } else { State::GapInB };
+        }
+        for j in 1..=m {
+            best_gap_a[cell(0, j)] = -(gap.open + (j - 1) as f64 * gap.extend);
+            from_gap_a[cell(0, j)] = if j == 1 { State::Match } else { State::GapInA };
+        }
+    }
+
+    let pick = |candidates: [(f64, State); 3]| {
+        candidates
+            .into_iter()
+            .fold((f64::NEG_INFINITY, State::Start), |best, c| if c.0 > best.0 { c } else { best })
+    };
+
+    let mut best_local = (0.0, 0, 0);
+    for i in 1..=n {
+        for j in 1..=m {
+            let diagonal = cell(i - 1, j - 1);
+            let (mut value, mut state) = pick([
+                (best_match[diagonal], State::Match),
+                (best_gap_b[diagonal], State::GapInB),
+                (best_gap_a[diagonal], State::GapInA),
+            ]);
+            if local && value <= 0.0 {
+                value = 0.0;
+                state = State::Start;
+            }
+            let here = cell(i, j);
+            best_match[here] = value + score(i - 1, j - 1);
+            from_match[here] = state;
+
+            let up = cell(i - 1, j);
+            (best_gap_b[here], from_gap_b[here]) = pick([
+                (best_match[up] - gap.open, State::Match),
+                (best_gap_b[up] - gap.extend, State::GapInB),
+                (best_gap_a[up] - gap.open, State::GapInA),
+            ]);
+
+            let left = cell(i, j - 1);
+            (best_gap_a[here], from_gap_a[here]) = pick([
+                (best_match[left] - gap.open, State::Match),
+                (best_gap_a[left] - gap.extend, State::GapInA),
+                (best_gap_b[left] - gap.open, State::GapInB),
+            ]);
+
+            if local && best_match[here] > best_local.0 {
+                best_local = (best_match[here], i, j);
+            }
+        }
+    }
+
+    let (score, mut i, mut j, mut state) = if local {
+        (best_local.0, best_local.1, best_local.2, State::Match)
+    } else {
+        let end = cell(n, m);
+        let (score, state) = pick([
+            (best_match[end], State::Match),
+            (best_gap_b[end], State::GapInB),
+            (best_gap_a[end], State::GapInA),
+        ]);
+        (score, n, m, if n == 0 && m == 0 { State::Start } else { state })
+    };
+    if local && score <= 0.0 {
+        state = State::Start;
+    }
+
+    let mut columns = Vec::new();
+    while state != State::Start && (i > 0 || j > 0) {
+        let here = cell(i, j);
+        match state {
+            State::Match => {
+                columns.push((Some(i - 1), Some(j - 1)));
+                state = from_match[here];
+                i -= 1;
+                j -= 1;
+            }
+            State::GapInB => {
+                columns.push((Some(i - 1), None));
+                state = from_gap_b[here];
+                i -= 1;
+            }
+            State::GapInA => {
+                columns.push((None, Some(j - 1)));
+                state = from_gap_a[here];
+                j -= 1;
+            }
+            State::Start => {}
+        }
+    }
+    columns.reverse();
+    DpResult { score, columns }
+}
+
+/// Pairwise and progressive aligner over one substitution matrix.
+#[derive(Debug, Clone, Default)]
+pub struct Aligner {
+    pub matrix: SubstitutionMatrix,
+    pub gap: GapPenalty,
+}
+
+impl Aligner {
+    pub fn new(matrix: SubstitutionMatrix) -> Self {
+        Self {
+            matrix,
+            gap: GapPenalty::default(),
+        }
+    }
+
+    pub fn with_gap_penalty(mut self, open: f64, extend: f64) -> Self {
+        self.gap = GapPenalty { open, extend };
+        self
+    }
+
+    pub fn align(&self, a: &str, b: &str, mode: AlignmentMode) -> PairwiseAlignment {
+        let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+        let result = gotoh(
+            a.len(),
+            b.len(),
+            |i, j| self.matrix.score(a[i], b[j]) as f64,
+            &self.gap,
+            mode,
+        );
+
+        let row = |residues: &[char], pick: fn(&Column) -> Option<usize>| -> String {
+            result
+                .columns
+                .iter()
+                .map(|column| pick(column).map_or(GAP, |index| residues[index]))
+                .collect()
+        };
+        let start = |pick: fn(&Column) -> Option<usize>| result.columns.iter().find_map(pick).unwrap_or(0);
+
+        PairwiseAlignment {
+            mode,
+            score: result.score,
+            aligned_a: row(&a, |c| c.0),
+            aligned_b: row(&b, |c| c.1),
+            start_a: start(|c| c.0),
+            start_b: start(|c| c.1),
+        }
+    }
+
+    pub fn global(&self, a: &str, b: &str) -> PairwiseAlignment {
+        self.align(a, b, AlignmentMode::Global)
+    }
+
+    pub fn local(&self, a: &str, b: &str) -> PairwiseAlignment {
+        self.align(a, b, AlignmentMode::Local)
+    }
+
+    pub fn align_proteins(&self, a: &Protein, b: &Protein, mode: AlignmentMode) -> PairwiseAlignment {
+        self.align(&a.sequence, &b.sequence, mode)
+    }
+
+    /// Mean substitution score over residue pairs of two profile columns;
+    /// pairs involving a gap score zero.
+    fn profile_score(&self, a: &[Vec<char>], b: &[Vec<char>], i: usize, j: usize) -> f64 {
+        let mut total = 0.0;
+        for row_a in a {
+            for row_b in b {
+                let (x, y) = (row_a[i], row_b[j]);
+                if x != GAP && y != GAP {
+                    total += self.matrix.score(x, y) as f64;
+                }
+            }
+        }
+        total / (a.len() * b.len()) as f64
+    }
+
+    fn align_profiles(&self, a: Profile, b: Profile) -> Profile {
+        let (n, m) = (a[0].len(), b[0].len());
+        let result = gotoh(n, m, |i, j| self.profile_score(&a, &b, i, j), &self.gap, AlignmentMode::Global);
+
+        let mut rows: Profile = vec![Vec::with_capacity(result.columns.len()); a.len() + b.len()];
+        for (column_a, column_b) in &result.columns {
+            for (row, source) in rows.iter_mut().zip(&a) {
+                row.push(column_a.map_or(GAP, |i| source[i]));
+            }
+            for (row, source) in rows[a.len()..].iter_mut().zip(&b) {
+                row.push(column_b.map_or(GAP, |j| source[j]));
+            }
+        }
+        rows
+    }
+
+    /// Progressive multiple alignment: pairwise global identities give a
+    /// UPGMA guide tree whose merges are aligned profile to profile.
+    pub fn progressive(&self, proteins: &[Protein]) -> MultipleAlignment {
+        let count = proteins.len();
+        let mut distances = vec![vec![0.0; count]; count];
+        for i in 0..count {
+            for j in i + 1..count {
+                let distance = 1.0 - self.align_proteins(&proteins[i], &proteins[j], AlignmentMode::Global).identity();
+                distances[i][j] = distance;
+                distances[j][i] = distance;
+            }
+        }
+
+        // Each cluster holds its member indices and their aligned rows.
+        let mut clusters: Vec<Option<(Vec<usize>, Profile)>> = proteins
+            .iter()
+            .enumerate()
+            .map(|(index, protein)| Some((vec![index], vec![protein.sequence.chars().collect()])))
+            .collect();
+
+        for _ in 1..count {
+            let mut closest = (f64::INFINITY, 0, 0);
+            for i in 0..count {
+                for j in i + 1..count {
+                    if clusters[i].is_some() && clusters[j].is_some() && distances[i][j] < closest.0 {
+                        closest = (distances[i][j], i, j);
+                    }
+                }
+            }
+            let (_, keep, merged) = closest;
+            let (members_b, rows_b) = clusters[merged].take().expect("cluster is active");
+            let (mut members_a, rows_a) = clusters[keep].take().expect("cluster is active");
+
+            let (size_a, size_b) = (members_a.len() as f64, members_b.len() as f64);
+            let averaged: Vec<f64> = distances[keep]
+                .iter()
+                .zip(&distances[merged])
+                .map(|(a, b)| (size_a * a + size_b * b) / (size_a + size_b))
+                .collect();
+            for (k, average) in averaged.into_iter().enumerate() {
+                distances[keep][k] = average;
+                distances[k][keep] = average;
+            }
+
+            let rows = self.align_profiles(rows_a, rows_b);
+            members_a.extend(members_b);
+            clusters[keep] = Some((members_a, rows));
+        }
+
+        let (members, rows) = clusters.into_iter().flatten().next().unwrap_or_default();
+        let mut ordered: Vec<(usize, String)> = members
+            .into_iter()
+            .zip(rows.into_iter().map(|row| row.into_iter().collect()))
+            .collect();
+        ordered.sort_by_key(|(index, _)| *index);
+
+        MultipleAlignment {
+            ids: ordered.iter().map(|(index, _)| proteins[*index].id.clone()).collect(),
+            rows: ordered.into_iter().map(|(_, row)| row).collect(),
+        }
+    }
+}
+
+/// Aligned rows in input order, all of equal length.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MultipleAlignment {
+    pub ids: Vec<String>,
+    pub rows: Vec<String>,
+}
+
+/// Mean conservation of the residues inside one domain.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DomainConservation {
+    pub domain_id: String,
+    pub name: String,
+    pub start: usize,
+    pub end: usize,
+    pub conservation: f64,
+}
+
+impl MultipleAlignment {
+    pub fn width(&self) -> usize {
+        self.rows.first().map_or(0, |row| row.chars().count())
+    }
+
+    /// Per-column conservation in `[0, 1]`: one minus the Shannon entropy
+    /// of the column's residues relative to 20 equiprobable residues,
+    /// scaled by the fraction of rows without a gap.
+    pub fn conservation(&self) -> Vec<f64> {
+        let rows: Vec<Vec<char>> = self.rows.iter().map(|row| row.chars().collect()).collect();
+        (0..self.width())
+            .map(|column| {
+                let mut counts: HashMap<char, usize> = HashMap::new();
+                let mut residues = 0;
+                for row in &rows {
+                    let residue = row.get(column).map_or(GAP, |r| r.to_ascii_uppercase());
+                    if residue != GAP {
+                        *counts.entry(residue).or_insert(0) += 1;
+                        residues += 1;
+                    }
+                }
+                if residues == 0 {
+                    return 0.0;
+                }
+                let entropy: f64 = counts
+                    .values()
+                    .map(|count| {
+                        let p = *count as f64 / residues as f64;
+                        -p * p.log2()
+                    })
+                    .sum();
+                (1.0 - entropy / 20f64.log2()) * residues as f64 / rows.len() as f64
+            })
+            .collect()
+    }
+
+    /// Conservation of each residue of the sequence with `id`, indexed by
+    /// 0-based position in the ungapped sequence.
+    pub fn residue_conservation(&self, id: &str) -> Result<Vec<f64>> {
+        let row = self
+            .ids
+            .iter()
+            .position(|row_id| row_id == id)
+            .ok_or_else(|| MCL1Error::UnknownProtein(id.to_string()))?;
+        let conservation = self.conservation();
+        Ok(self.rows[row]
+            .chars()
+            .zip(conservation)
+            .filter(|(residue, _)| *residue != GAP)
+            .map(|(_, score)| score)
+            .collect())
+    }
+
+    /// Mean residue conservation over each of `protein`'s domains.
+    pub fn domain_conservation(&self, protein: &Protein) -> Result<Vec<DomainConservation>> {
+        let residues = self.residue_conservation(&protein.id)?;
+        Ok(protein
+            .domains
+            .iter()
+            .map(|domain| {
+                let range = domain.start.max(1) - 1..domain.end.min(residues.len());
+                let scores = residues.get(range).unwrap_or(&[]);
+                let conservation = if scores.is_empty() {
+                    0.0
+                } else {
+                    scores.iter().sum::<f64>() / scores.len() as f64
+                };
+                DomainConservation {
+                    domain_id: domain.id.clone(),
+                    name: domain.name.clone(),
+                    start: domain.start,
+                    end: domain.end,
+                    conservation,
+                }
+            })
+            .collect())
+    }
+}
@@ -1,11 +1,31 @@
-use crate::models::{ProteinInteraction, DrugTarget};
+use crate::analysis::interaction_network::ProteinInteraction;
+use crate::error::Result;
+use crate::models::isoform::{ExpressionTable, Gene, IsoformFunction};
+use crate::models::DrugTarget;
 use crate::utils::math::calculate_cardiotoxicity_score;
 
+/// Default cardiotoxicity score at which a target is reported.
+pub const DEFAULT_CARDIOTOXICITY_THRESHOLD: f64 = 0.5;
+
+/// Predicted targets for one isoform.
+#[derive(Debug, Clone)]
+pub struct IsoformCardiotoxicity {
+    pub isoform: String,
+    pub function: IsoformFunction,
+    pub targets: Vec<DrugTarget>,
+}
+
 #[derive(Debug, Clone)]
 pub struct CardiotoxicityPredictor {
     pub threshold: f64,
 }
 
+impl Default for CardiotoxicityPredictor {
+    fn default() -> Self {
+        Self::new(DEFAULT_CARDIOTOXICITY_THRESHOLD)
+    }
+}
+
 impl CardiotoxicityPredictor {
     pub fn new(threshold: f64) -> Self {
         Self { threshold }
@@ -17,15 +37,36 @@ impl CardiotoxicityPredictor {
             .filter_map(|interaction| {
                 let score = calculate_cardiotoxicity_score(interaction);
                 if score >= self.threshold {
-                    Some(DrugTarget {
-                        target_id: interaction.target.clone(),
-                        cardiotoxicity_score: score,
-                        confidence: 0.95,
-                    })
+                    Some(DrugTarget::new(
+                        format!("{}-{}", interaction.source, interaction.target),
+                        interaction.target.clone(),
+                        interaction.target.clone(),
+                        interaction.interaction_type.clone(),
+                        score as f32,
+                        interaction.confidence as f32,
+                        0.95,
+                    ))
                 } else {
                     None
                 }
             })
             .collect()
     }
-}
\ No newline at end of file
+
+    /// Runs the prediction on each isoform's own interactions.
+    pub fn predict_isoforms(&self, gene: &Gene) -> Vec<IsoformCardiotoxicity> {
+        gene.isoforms
+            .iter()
+            .map(|isoform| IsoformCardiotoxicity {
+                isoform: isoform.name.clone(),
+                function: isoform.function,
+                targets: self.predict_cardiotoxicity(&isoform.interactions(&isoform.name)),
+            })
+            .collect()
+    }
+
+    /// Runs the prediction on the gene's expression-weighted interactions.
+    pub fn predict_weighted(&self, gene: &Gene, expression: &ExpressionTable) -> Result<Vec<DrugTarget>> {
+        Ok(self.predict_cardiotoxicity(&gene.weighted_interactions(expression)?))
+    }
+}
@@ -0,0 +1,354 @@
+//! Node centrality measures on `InteractionNetwork`
+//!
+//! The network is treated as undirected with parallel edges merged.
+//! Strength-based measures (weighted degree, eigenvector, PageRank) weight
+//! links by their summed confidence. Path-based measures (betweenness,
+//! closeness) give each link a length of `1 / confidence`, so confident
+//! links are short. Zero-confidence links are never traversed.
+
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::analysis::interaction_network::{Cost, InteractionNetwork, Link};
+use crate::error::{MCL1Error, Result};
+
+pub const PAGERANK_DAMPING: f64 = 0.85;
+
+/// Convergence threshold (L1 change per iteration) for iterative measures.
+const TOLERANCE: f64 = 1e-10;
+const MAX_ITERATIONS: usize = 1000;
+/// Relative slack when deciding two weighted paths are equally short.
+const PATH_EPSILON: f64 = 1e-9;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CentralityMeasure {
+    Degree,
+    WeightedDegree,
+    Betweenness,
+    Closeness,
+    Eigenvector,
+    PageRank,
+}
+
+impl CentralityMeasure {
+    pub const ALL: [CentralityMeasure; 6] = [
+        CentralityMeasure::Degree,
+        CentralityMeasure::WeightedDegree,
+        CentralityMeasure::Betweenness,
+        CentralityMeasure::Closeness,
+        CentralityMeasure::Eigenvector,
+        CentralityMeasure::PageRank,
+    ];
+
+    /// Attribute key the measure is stored under in `node_attributes`.
+    pub fn label(&self) -> &'static str {
+        match self {
+            CentralityMeasure::Degree => "degree",
+            CentralityMeasure::WeightedDegree => "weighted_degree",
+            CentralityMeasure::Betweenness => "betweenness",
+            CentralityMeasure::Closeness => "closeness",
+            CentralityMeasure::Eigenvector => "eigenvector",
+            CentralityMeasure::PageRank => "pagerank",
+        }
+    }
+}
+
+/// Every centrality measure for one node.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CentralityRow {
+    pub node: String,
+    /// Number of distinct neighbors.
+    pub degree: usize,
+    pub weighted_degree: f64,
+    /// Normalized by `(n - 1)(n - 2) / 2`, the number of pairs it could lie between.
+    pub betweenness: f64,
+    /// Wasserman–Faust closeness, scaled by the reachable fraction of the network.
+    pub closeness: f64,
+    /// Unit-length principal eigenvector entry.
+    pub eigenvector: f64,
+    pub pagerank: f64,
+}
+
+impl CentralityRow {
+    pub fn score(&self, measure: CentralityMeasure) -> f64 {
+        match measure {
+            CentralityMeasure::Degree => self.degree as f64,
+            CentralityMeasure::WeightedDegree => self.weighted_degree,
+            CentralityMeasure::Betweenness => self.betweenness,
+            CentralityMeasure::Closeness => self.closeness,
+            CentralityMeasure::Eigenvector => self.eigenvector,
+            CentralityMeasure::PageRank => self.pagerank,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CentralityTable {
+    pub rows: Vec<CentralityRow>,
+}
+
+impl CentralityTable {
+    pub fn row(&self, node: &str) -> Option<&CentralityRow> {
+        self.rows.iter().find(|row| row.node == node)
+    }
+
+    /// Rows from most to least central, ties broken by node name.
+    pub fn ranked(&self, measure: CentralityMeasure) -> Vec<&CentralityRow> {
+        let mut ranked: Vec<&CentralityRow> = self.rows.iter().collect();
+        ranked.sort_by(|a, b| {
+            b.score(measure)
+                .total_cmp(&a.score(measure))
+                .then_with(|| a.node.cmp(&b.node))
+        });
+        ranked
+    }
+
+    /// 1-based rank of `node` under `measure`.
+    pub fn rank_of(&self, node: &str, measure: CentralityMeasure) -> Option<usize> {
+        self.ranked(measure).iter().position(|row| row.node == node).map(|i| i + 1)
+    }
+
+    /// Rows for `nodes` only, keeping their network-wide scores.
+    pub fn restricted_to(&self, nodes: &[&str]) -> Self {
+        Self {
+            rows: self.rows.iter().filter(|row| nodes.contains(&row.node.as_str())).cloned().collect(),
+        }
+    }
+}
+
+impl InteractionNetwork {
+    fn by_name<T: Copy>(&self, values: Vec<T>) -> HashMap<String, T> {
+        self.nodes().iter().cloned().zip(values).collect()
+    }
+
+    pub fn degree_centrality(&self) -> HashMap<String, usize> {
+        self.by_name(self.links().iter().map(Vec::len).collect())
+    }
+
+    pub fn weighted_degree_centrality(&self) -> HashMap<String, f64> {
+        self.by_name(weighted_degrees(&self.links()))
+    }
+
+    pub fn betweenness_centrality(&self) -> HashMap<String, f64> {
+        self.by_name(betweenness(&self.links()))
+    }
+
+    pub fn closeness_centrality(&self) -> HashMap<String, f64> {
+        self.by_name(closeness(&self.links()))
+    }
+
+    pub fn eigenvector_centrality(&self) -> HashMap<String, f64> {
+        self.by_name(eigenvector(&self.links()))
+    }
+
+    /// PageRank with the given damping factor; scores sum to one.
+    pub fn pagerank(&self, damping: f64) -> Result<HashMap<String, f64>> {
+        MCL1Error::check_range("damping", damping, 0.0, 1.0)?;
+        Ok(self.by_name(pagerank(&self.links(), damping)))
+    }
+
+    /// Every measure for every node, in node order.
+    pub fn centrality_table(&self) -> CentralityTable {
+        let links = self.links();
+        let weighted = weighted_degrees(&links);
+        let betweenness = betweenness(&links);
+        let closeness = closeness(&links);
+        let eigenvector = eigenvector(&links);
+        let pagerank = pagerank(&links, PAGERANK_DAMPING);
+
+        let rows = (0..self.node_count())
+            .map(|i| CentralityRow {
+                node: self.node_name(i).to_string(),
+                degree: links[i].len(),
+                weighted_degree: weighted[i],
+                betweenness: betweenness[i],
+                closeness: closeness[i],
+                eigenvector: eigenvector[i],
+                pagerank: pagerank[i],
+            })
+            .collect();
+        CentralityTable { rows }
+    }
+
+    /// Computes the centrality table and stores each measure as a node
+    /// attribute keyed by `CentralityMeasure::label`.
+    pub fn annotate_centrality(&mut self) -> CentralityTable {
+        let table = self.centrality_table();
+        for row in &table.rows {
+            for measure in CentralityMeasure::ALL {
+                self.add_node_attribute(&row.node, measure.label(), &row.score(measure).to_string());
+            }
+        }
+        table
+    }
+
+    /// Network-wide centrality of `node`'s direct partners, for ranking
+    /// co-targets by how central they are around it.
+    pub fn partner_centrality(&self, node: &str) -> Result<CentralityTable> {
+        if !self.contains_node(node) {
+            return Err(MCL1Error::UnknownProtein(node.to_string()));
+        }
+        let partners: Vec<&str> = self
+            .get_neighbors(node)
+            .into_iter()
+            .map(String::as_str)
+            .filter(|partner| *partner != node)
+            .collect();
+        Ok(self.centrality_table().restricted_to(&partners))
+    }
+}
+
+fn weighted_degrees(links: &[Vec<Link>]) -> Vec<f64> {
+    links.iter().map(|links| links.iter().map(|l| l.weight).sum()).collect()
+}
+
+/// Brandes' algorithm with Dijkstra for the single-source stage.
+fn betweenness(links: &[Vec<Link>]) -> Vec<f64> {
+    let n = links.len();
+    let mut centrality = vec![0.0; n];
+
+    for source in 0..n {
+        let mut distance = vec![f64::INFINITY; n];
+        let mut paths = vec![0.0; n];
+        let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); n];
+        let mut settled = vec![false; n];
+        let mut order = Vec::new();
+        let mut heap = BinaryHeap::new();
+
+        distance[source] = 0.0;
+        paths[source] = 1.0;
+        heap.push(Reverse((Cost(0.0), source)));
+        while let Some(Reverse((Cost(d), v))) = heap.pop() {
+            if settled[v] {
+                continue;
+            }
+            settled[v] = true;
+            order.push(v);
+            for link in links[v].iter().filter(|l| l.confidence > 0.0) {
+                let w = link.node;
+                let candidate = d + 1.0 / link.confidence;
+                if candidate < distance[w] * (1.0 - PATH_EPSILON) {
+                    distance[w] = candidate;
+                    paths[w] = paths[v];
+                    predecessors[w] = vec![v];
+                    heap.push(Reverse((Cost(candidate), w)));
+                } else if !settled[w] && candidate <= distance[w] * (1.0 + PATH_EPSILON) {
+                    paths[w] += paths[v];
+                    predecessors[w].push(v);
+                }
+            }
+        }
+
+        let mut dependency = vec![0.0; n];
+        for &w in order.iter().rev() {
+            for &v in &predecessors[w] {
+                dependency[v] += paths[v] / paths[w] * (1.0 + dependency[w]);
+            }
+            if w != source {
+                centrality[w] += dependency[w];
+            }
+        }
+    }
+
+    // Each unordered pair was counted from both ends.
+    if n > 2 {
+        let scale = 1.0 / ((n - 1) * (n - 2)) as f64;
+        centrality.iter_mut().for_each(|c| *c *= scale);
+    }
+    centrality
+}
+
+fn shortest_distances(links: &[Vec<Link>], source: usize) -> Vec<f64> {
+    let mut distance = vec![f64::INFINITY; links.len()];
+    let mut heap = BinaryHeap::new();
+    distance[source] = 0.0;
+    heap.push(Reverse((Cost(0.0), source)));
+    while let Some(Reverse((Cost(d), v))) = heap.pop() {
+        if d > distance[v] {
+            continue;
+        }
+        for link in links[v].iter().filter(|l| l.confidence > 0.0) {
+            let candidate = d + 1.0 / link.confidence;
+            if candidate < distance[link.node] {
+                distance[link.node] = candidate;
+                heap.push(Reverse((Cost(candidate), link.node)));
+            }
+        }
+    }
+    distance
+}
+
+fn closeness(links: &[Vec<Link>]) -> Vec<f64> {
+    let n = links.len();
+    (0..n)
+        .map(|source| {
+            let reachable: Vec<f64> = shortest_distances(links, source)
+                .into_iter()
+                .filter(|d| d.is_finite() && *d > 0.0)
+                .collect();
+            let total: f64 = reachable.iter().sum();
+            if total == 0.0 || n < 2 {
+                return 0.0;
+            }
+            let r = reachable.len() as f64;
+            (r / total) * (r / (n - 1) as f64)
+        })
+        .collect()
+}
+
+/// Power iteration on `A + I`, which shares A's principal eigenvector but
+/// also converges on bipartite graphs.
+fn eigenvector(links: &[Vec<Link>]) -> Vec<f64> {
+    let n = links.len();
+    if n == 0 {
+        return Vec::new();
+    }
+    let mut scores = vec![1.0 / n as f64; n];
+    for _ in 0..MAX_ITERATIONS {
+        let mut next = scores.clone();
+        for (v, links) in links.iter().enumerate() {
+            for link in links {
+                next[link.node] += scores[v] * link.weight;
+            }
+        }
+        let norm = next.iter().map(|x| x * x).sum::<f64>().sqrt();
+        if norm == 0.0 {
+            return vec![0.0; n];
+        }
+        next.iter_mut().for_each(|x| *x /= norm);
+        let change: f64 = next.iter().zip(&scores).map(|(a, b)| (a - b).abs()).sum();
+        scores = next;
+        if change < TOLERANCE * n as f64 {
+            break;
+        }
+    }
+    scores
+}
+
+/// Weighted PageRank; nodes without links spread their rank uniformly.
+fn pagerank(links: &[Vec<Link>], damping: f64) -> Vec<f64> {
+    let n = links.len();
+    if n == 0 {
+        return Vec::new();
+    }
+    let restart = vec![1.0 / n as f64; n];
+    let strength = weighted_degrees(links);
+
+    let mut scores = restart.to_vec();
+    for _ in 0..MAX_ITERATIONS {
+        let dangling: f64 = (0..n).filter(|&v| strength[v] == 0.0).map(|v| scores[v]).sum();
+        let mut next: Vec<f64> = restart.iter().map(|r| (1.0 - damping + damping * dangling) * r).collect();
+        for (v, links) in links.iter().enumerate().filter(|(v, _)| strength[*v] > 0.0) {
+            for link in links {
+                next[link.node] += damping * scores[v] * link.weight / strength[v];
+            }
+        }
+        let change: f64 = next.iter().zip(&scores).map(|(a, b)| (a - b).abs()).sum();
+        scores = next;
+        if change < TOLERANCE {
+            break;
+        }
+    }
+    scores
+}
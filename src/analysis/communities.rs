@@ -0,0 +1,378 @@
+//! Module detection on `InteractionNetwork`
+//!
+//! Modularity clustering (Louvain, and Louvain with Leiden's connectivity
+//! refinement), label propagation and k-core decomposition. The network is
+//! treated as undirected with parallel edges merged, weighted by summed
+//! confidence. Module IDs are numbered from 0 by decreasing module size.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::analysis::interaction_network::{InteractionNetwork, Link};
+use crate::error::{MCL1Error, Result};
+
+const MAX_ITERATIONS: usize = 100;
+/// Smallest modularity gain that counts as an improvement.
+const MIN_GAIN: f64 = 1e-12;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CommunityMethod {
+    Louvain,
+    /// Louvain whose communities are split into connected parts before each
+    /// aggregation, which is the guarantee Leiden's refinement phase gives;
+    /// the randomized merging within the refinement is not performed.
+    Leiden,
+    LabelPropagation,
+}
+
+impl CommunityMethod {
+    /// Attribute key module IDs are stored under in `node_attributes`.
+    pub fn label(&self) -> &'static str {
+        match self {
+            CommunityMethod::Louvain => "louvain_module",
+            CommunityMethod::Leiden => "leiden_module",
+            CommunityMethod::LabelPropagation => "label_propagation_module",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Communities {
+    pub method: CommunityMethod,
+    pub membership: HashMap<String, usize>,
+    /// Modularity of the partition at resolution 1.
+    pub modularity: f64,
+}
+
+impl Communities {
+    pub fn module_of(&self, node: &str) -> Option<usize> {
+        self.membership.get(node).copied()
+    }
+
+    pub fn module_count(&self) -> usize {
+        self.membership.values().max().map_or(0, |max| max + 1)
+    }
+
+    /// Sorted members of each module, indexed by module ID.
+    pub fn modules(&self) -> Vec<Vec<String>> {
+        let mut modules = vec![Vec::new(); self.module_count()];
+        for (node, &module) in &self.membership {
+            modules[module].push(node.clone());
+        }
+        modules.iter_mut().for_each(|members| members.sort());
+        modules
+    }
+}
+
+/// Weighted undirected graph with self-loops, as produced by aggregation.
+struct Graph {
+    adjacency: Vec<Vec<(usize, f64)>>,
+    self_loops: Vec<f64>,
+}
+
+impl Graph {
+    fn from_links(links: &[Vec<Link>]) -> Self {
+        Self {
+            adjacency: links.iter().map(|l| l.iter().map(|link| (link.node, link.weight)).collect()).collect(),
+            self_loops: vec![0.0; links.len()],
+        }
+    }
+
+    fn strength(&self, node: usize) -> f64 {
+        self.adjacency[node].iter().map(|(_, w)| w).sum::<f64>() + 2.0 * self.self_loops[node]
+    }
+
+    /// Collapses each community into one node.
+    fn aggregate(&self, community: &[usize], count: usize) -> Self {
+        let mut adjacency: Vec<BTreeMap<usize, f64>> = vec![BTreeMap::new(); count];
+        let mut self_loops = vec![0.0; count];
+        for (node, neighbors) in self.adjacency.iter().enumerate() {
+            let c = community[node];
+            self_loops[c] += self.self_loops[node];
+            for &(neighbor, weight) in neighbors {
+                let d = community[neighbor];
+                if c == d {
+                    // Seen from both ends.
+                    self_loops[c] += weight / 2.0;
+                } else {
+                    *adjacency[c].entry(d).or_insert(0.0) += weight;
+                }
+            }
+        }
+        Self {
+            adjacency: adjacency.into_iter().map(|m| m.into_iter().collect()).collect(),
+            self_loops,
+        }
+    }
+}
+
+/// Renumbers labels to 0.. by decreasing group size, ties by first member.
+fn renumber(labels: &[usize]) -> Vec<usize> {
+    let mut groups: BTreeMap<usize, (usize, usize)> = BTreeMap::new();
+    for (node, &label) in labels.iter().enumerate() {
+        groups.entry(label).or_insert((0, node)).0 += 1;
+    }
+    let mut order: Vec<(usize, (usize, usize))> = groups.into_iter().collect();
+    order.sort_by(|a, b| b.1 .0.cmp(&a.1 .0).then(a.1 .1.cmp(&b.1 .1)));
+    let ids: HashMap<usize, usize> = order.iter().enumerate().map(|(id, (label, _))| (*label, id)).collect();
+    labels.iter().map(|label| ids[label]).collect()
+}
+
+/// One round of Louvain local moving; returns community labels and
+/// whether any node moved.
+fn local_moving(graph: &Graph, resolution: f64) -> (Vec<usize>, bool) {
+    let n = graph.adjacency.len();
+    let strength: Vec<f64> = (0..n).map(|v| graph.strength(v)).collect();
+    let total: f64 = strength.iter().sum();
+    let mut community: Vec<usize> = (0..n).collect();
+    let mut community_strength = strength.clone();
+    let mut moved_any = false;
+    if total == 0.0 {
+        return (community, false);
+    }
+
+    for _ in 0..MAX_ITERATIONS {
+        let mut moved = false;
+        for v in 0..n {
+            let current = community[v];
+            let mut weights: BTreeMap<usize, f64> = BTreeMap::new();
+            for &(neighbor, weight) in &graph.adjacency[v] {
+                *weights.entry(community[neighbor]).or_insert(0.0) += weight;
+            }
+            community_strength[current] -= strength[v];
+
+            let gain = |c: usize, w: f64| w - resolution * community_strength[c] * strength[v] / total;
+            let mut best = (current, gain(current, weights.get(&current).copied().unwrap_or(0.0)));
+            for (&c, &w) in &weights {
+                let g = gain(c, w);
+                if g > best.1 + MIN_GAIN {
+                    best = (c, g);
+                }
+            }
+
+            community_strength[best.0] += strength[v];
+            if best.0 != current {
+                community[v] = best.0;
+                moved = true;
+            }
+        }
+        if !moved {
+            break;
+        }
+        moved_any = true;
+    }
+    (community, moved_any)
+}
+
+/// Splits each community into its connected parts.
+fn refine(graph: &Graph, community: &[usize]) -> Vec<usize> {
+    let n = community.len();
+    let mut refined = vec![usize::MAX; n];
+    let mut next = 0;
+    for start in 0..n {
+        if refined[start] != usize::MAX {
+            continue;
+        }
+        refined[start] = next;
+        let mut stack = vec![start];
+        while let Some(v) = stack.pop() {
+            for &(neighbor, _) in &graph.adjacency[v] {
+                if refined[neighbor] == usize::MAX && community[neighbor] == community[v] {
+                    refined[neighbor] = next;
+                    stack.push(neighbor);
+                }
+            }
+        }
+        next += 1;
+    }
+    refined
+}
+
+fn modularity_clustering(links: &[Vec<Link>], resolution: f64, connected: bool) -> Vec<usize> {
+    let mut membership: Vec<usize> = (0..links.len()).collect();
+    let mut graph = Graph::from_links(links);
+    loop {
+        let (mut community, moved) = local_moving(&graph, resolution);
+        if connected {
+            community = refine(&graph, &community);
+        }
+        if !moved {
+            break;
+        }
+        let community = renumber(&community);
+        let count = community.iter().max().map_or(0, |max| max + 1);
+        if count == community.len() {
+            break;
+        }
+        membership.iter_mut().for_each(|m| *m = community[*m]);
+        graph = graph.aggregate(&community, count);
+    }
+    renumber(&membership)
+}
+
+/// Asynchronous label propagation in node order: each node takes the label
+/// with the most weight among its neighbors, keeping its own on ties and
+/// otherwise preferring the smallest label.
+fn label_propagation(links: &[Vec<Link>]) -> Vec<usize> {
+    let mut labels: Vec<usize> = (0..links.len()).collect();
+    for _ in 0..MAX_ITERATIONS {
+        let mut changed = false;
+        for v in 0..links.len() {
+            let mut weights: BTreeMap<usize, f64> = BTreeMap::new();
+            for link in &links[v] {
+                *weights.entry(labels[link.node]).or_insert(0.0) += link.weight;
+            }
+            let Some(max) = weights.values().copied().reduce(f64::max) else {
+                continue;
+            };
+            let is_best = |label: &usize| (weights[label] - max).abs() <= MIN_GAIN;
+            if weights.contains_key(&labels[v]) && is_best(&labels[v]) {
+                continue;
+            }
+            if let Some(&label) = weights.keys().find(|label| is_best(label)) {
+                labels[v] = label;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    renumber(&labels)
+}
+
+fn modularity(links: &[Vec<Link>], membership: &[usize]) -> f64 {
+    let strength: Vec<f64> = links.iter().map(|l| l.iter().map(|link| link.weight).sum()).collect();
+    let total: f64 = strength.iter().sum();
+    if total == 0.0 {
+        return 0.0;
+    }
+    let internal: f64 = links
+        .iter()
+        .enumerate()
+        .flat_map(|(v, l)| l.iter().filter(move |link| membership[link.node] == membership[v]))
+        .map(|link| link.weight)
+        .sum();
+    let mut module_strength: HashMap<usize, f64> = HashMap::new();
+    for (v, s) in strength.iter().enumerate() {
+        *module_strength.entry(membership[v]).or_insert(0.0) += s;
+    }
+    internal / total - module_strength.values().map(|s| (s / total).powi(2)).sum::<f64>()
+}
+
+/// Core number of every node (Batagelj–Zaversnik peeling).
+fn core_numbers(links: &[Vec<Link>]) -> Vec<usize> {
+    let n = links.len();
+    let mut degree: Vec<usize> = links.iter().map(Vec::len).collect();
+    let mut core = vec![0; n];
+    let mut removed = vec![false; n];
+    let max_degree = degree.iter().copied().max().unwrap_or(0);
+    let mut buckets: Vec<Vec<usize>> = vec![Vec::new(); max_degree + 1];
+    for (v, &d) in degree.iter().enumerate() {
+        buckets[d].push(v);
+    }
+
+    let mut k = 0;
+    let mut level = 0;
+    while level <= max_degree {
+        let Some(v) = buckets[level].pop() else {
+            level += 1;
+            continue;
+        };
+        if removed[v] || degree[v] != level {
+            continue;
+        }
+        k = k.max(level);
+        core[v] = k;
+        removed[v] = true;
+        for link in &links[v] {
+            let u = link.node;
+            if !removed[u] && degree[u] > 0 {
+                degree[u] -= 1;
+                buckets[degree[u]].push(u);
+                level = level.min(degree[u]);
+            }
+        }
+    }
+    core
+}
+
+impl InteractionNetwork {
+    fn communities_from(&self, method: CommunityMethod, links: &[Vec<Link>], membership: Vec<usize>) -> Communities {
+        Communities {
+            method,
+            modularity: modularity(links, &membership),
+            membership: self.nodes().iter().cloned().zip(membership).collect(),
+        }
+    }
+
+    /// Louvain modularity clustering; higher `resolution` gives smaller modules.
+    pub fn louvain(&self, resolution: f64) -> Result<Communities> {
+        MCL1Error::check_range("resolution", resolution, f64::MIN_POSITIVE, f64::INFINITY)?;
+        let links = self.links();
+        let membership = modularity_clustering(&links, resolution, false);
+        Ok(self.communities_from(CommunityMethod::Louvain, &links, membership))
+    }
+
+    /// As `louvain`, but every module is guaranteed to be connected.
+    pub fn leiden(&self, resolution: f64) -> Result<Communities> {
+        MCL1Error::check_range("resolution", resolution, f64::MIN_POSITIVE, f64::INFINITY)?;
+        let links = self.links();
+        let membership = modularity_clustering(&links, resolution, true);
+        Ok(self.communities_from(CommunityMethod::Leiden, &links, membership))
+    }
+
+    pub fn label_propagation(&self) -> Communities {
+        let links = self.links();
+        let membership = label_propagation(&links);
+        self.communities_from(CommunityMethod::LabelPropagation, &links, membership)
+    }
+
+    /// Largest `k` such that each node belongs to a subgraph where every
+    /// node has at least `k` distinct neighbors.
+    pub fn k_core_decomposition(&self) -> HashMap<String, usize> {
+        self.nodes().iter().cloned().zip(core_numbers(&self.links())).collect()
+    }
+
+    /// Stores each node's module ID under `communities.method.label()`.
+    pub fn annotate_modules(&mut self, communities: &Communities) {
+        for (node, module) in &communities.membership {
+            self.add_node_attribute(node, communities.method.label(), &module.to_string());
+        }
+    }
+
+    /// Computes core numbers and stores them under `k_core`.
+    pub fn annotate_k_cores(&mut self) -> HashMap<String, usize> {
+        let cores = self.k_core_decomposition();
+        for (node, core) in &cores {
+            self.add_node_attribute(node, "k_core", &core.to_string());
+        }
+        cores
+    }
+
+    /// Link weight from `node` into each module, heaviest first.
+    pub fn module_links(&self, node: &str, communities: &Communities) -> Result<Vec<(usize, f64)>> {
+        let index = self.node_index(node).ok_or_else(|| MCL1Error::UnknownProtein(node.to_string()))?;
+        let mut weights: BTreeMap<usize, f64> = BTreeMap::new();
+        for link in &self.links()[index] {
+            if let Some(module) = communities.module_of(self.node_name(link.node)) {
+                *weights.entry(module).or_insert(0.0) += link.weight;
+            }
+        }
+        let mut weights: Vec<(usize, f64)> = weights.into_iter().collect();
+        weights.sort_by(|a, b| b.1.total_cmp(&a.1));
+        Ok(weights)
+    }
+
+    /// Guimerà–Amaral participation coefficient: 0 when all of `node`'s
+    /// links stay in one module, approaching 1 as they spread evenly
+    /// across many, so high values mark bridges between modules.
+    pub fn participation_coefficient(&self, node: &str, communities: &Communities) -> Result<f64> {
+        let weights = self.module_links(node, communities)?;
+        let total: f64 = weights.iter().map(|(_, w)| w).sum();
+        if total == 0.0 {
+            return Ok(0.0);
+        }
+        Ok(1.0 - weights.iter().map(|(_, w)| (w / total).powi(2)).sum::<f64>())
+    }
+}
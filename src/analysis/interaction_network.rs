@@ -1,101 +1,579 @@
-use std::collections::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
 
-#[derive(Debug, Clone)]
+use crate::error::Result;
+use crate::models::interaction::{Direction, InteractionEdge, Sign};
+use crate::models::isoform::{ExpressionTable, Gene, Isoform};
+use crate::models::protein::InteractionType;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ProteinInteraction {
     pub source: String,
     pub target: String,
     pub interaction_type: String,
     pub confidence: f64,
+    /// Overrides the direction implied by `interaction_type`.
+    #[serde(default)]
+    pub direction: Option<Direction>,
+    /// Overrides the sign implied by `interaction_type`.
+    #[serde(default)]
+    pub sign: Option<Sign>,
+}
+
+impl ProteinInteraction {
+    pub fn new(source: &str, target: &str, interaction_type: &str, confidence: f64) -> Self {
+        Self {
+            source: source.to_string(),
+            target: target.to_string(),
+            interaction_type: interaction_type.to_string(),
+            confidence,
+            direction: None,
+            sign: None,
+        }
+    }
+
+    pub fn with_direction(mut self, direction: Direction) -> Self {
+        self.direction = Some(direction);
+        self
+    }
+
+    pub fn with_sign(mut self, sign: Sign) -> Self {
+        self.sign = Some(sign);
+        self
+    }
+
+    pub fn kind(&self) -> InteractionType {
+        InteractionType::from_label(&self.interaction_type)
+    }
+
+    /// Explicit direction, else the default for `interaction_type`.
+    pub fn direction(&self) -> Direction {
+        self.direction.unwrap_or_else(|| self.kind().default_direction())
+    }
+
+    /// Explicit sign, else the default for `interaction_type`.
+    pub fn sign(&self) -> Sign {
+        self.sign.unwrap_or_else(|| self.kind().default_sign())
+    }
+
+    /// True if the edge can be followed from `node`: undirected edges in
+    /// either direction, directed ones from their source.
+    pub fn leaves(&self, node: &str) -> bool {
+        self.source == node || (self.target == node && self.direction() == Direction::Undirected)
+    }
+
+    /// True if the edge can be followed into `node`.
+    pub fn enters(&self, node: &str) -> bool {
+        self.target == node || (self.source == node && self.direction() == Direction::Undirected)
+    }
+}
+
+/// Limits for neighborhood extraction; the default follows every edge
+/// without a hop or size limit.
+#[derive(Debug, Clone, Default)]
+pub struct SubnetworkFilter {
+    /// Hops from the nearest seed; `None` is unlimited.
+    pub max_depth: Option<usize>,
+    pub min_confidence: f64,
+    /// Interaction types to follow, matched case-insensitively against the
+    /// raw label or its canonical `InteractionType` label; empty allows all.
+    pub interaction_types: Vec<String>,
+    /// Cap on returned nodes, seeds included.
+    pub max_nodes: Option<usize>,
+}
+
+impl SubnetworkFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    pub fn with_min_confidence(mut self, confidence: f64) -> Self {
+        self.min_confidence = confidence;
+        self
+    }
+
+    pub fn with_interaction_types(mut self, types: &[&str]) -> Self {
+        self.interaction_types = types.iter().map(|t| t.to_string()).collect();
+        self
+    }
+
+    pub fn with_max_nodes(mut self, nodes: usize) -> Self {
+        self.max_nodes = Some(nodes);
+        self
+    }
+
+    pub fn allows(&self, edge: &ProteinInteraction) -> bool {
+        let canonical = edge.kind().label();
+        edge.confidence >= self.min_confidence
+            && (self.interaction_types.is_empty()
+                || self
+                    .interaction_types
+                    .iter()
+                    .any(|t| t.eq_ignore_ascii_case(&edge.interaction_type) || t.eq_ignore_ascii_case(canonical)))
+    }
+}
+
+/// Parallel edges between two nodes merged into one undirected link.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Link {
+    pub node: usize,
+    /// Summed confidence of the merged edges.
+    pub weight: f64,
+    /// Highest confidence among the merged edges.
+    pub confidence: f64,
+}
+
+/// Path length ordered with `f64::total_cmp`, for use as a heap key.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Cost(pub f64);
+
+impl Eq for Cost {}
+
+impl PartialOrd for Cost {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Cost {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.total_cmp(&other.0)
+    }
 }
 
-#[derive(Debug)]
+/// Interaction graph with interned node IDs and per-node adjacency lists,
+/// so neighbor and degree queries only touch the node's own edges.
+#[derive(Debug, Clone, Default)]
 pub struct InteractionNetwork {
-    pub nodes: HashSet<String>,
-    pub edges: Vec<ProteinInteraction>,
+    node_names: Vec<String>,
+    node_index: HashMap<String, usize>,
+    edges: Vec<ProteinInteraction>,
+    /// `(neighbor, edge)` index pairs per node, in edge insertion order; a
+    /// self-loop is listed once.
+    adjacency: Vec<Vec<(usize, usize)>>,
     pub node_attributes: HashMap<String, HashMap<String, String>>,
 }
 
+impl From<&ProteinInteraction> for InteractionEdge {
+    fn from(interaction: &ProteinInteraction) -> Self {
+        let mut edge = Self::from_label(
+            &interaction.source,
+            &interaction.target,
+            &interaction.interaction_type,
+            interaction.confidence,
+        );
+        edge.direction = interaction.direction();
+        edge.sign = interaction.sign();
+        edge
+    }
+}
+
+impl From<&InteractionEdge> for ProteinInteraction {
+    fn from(edge: &InteractionEdge) -> Self {
+        // Only keep direction and sign where the label would not imply them.
+        let kind = InteractionType::from_label(&edge.label);
+        ProteinInteraction {
+            source: edge.source.clone(),
+            target: edge.target.clone(),
+            interaction_type: edge.label.clone(),
+            confidence: edge.confidence,
+            direction: Some(edge.direction).filter(|d| *d != kind.default_direction()),
+            sign: Some(edge.sign).filter(|s| *s != kind.default_sign()),
+        }
+    }
+}
+
+impl Isoform {
+    /// The isoform's edges with the isoform renamed to `node`.
+    pub fn interactions(&self, node: &str) -> Vec<ProteinInteraction> {
+        let rename = |id: &String| if *id == self.name { node.to_string() } else { id.clone() };
+        self.edges()
+            .iter()
+            .map(|edge| {
+                let mut interaction = ProteinInteraction::from(edge);
+                interaction.source = rename(&interaction.source);
+                interaction.target = rename(&interaction.target);
+                interaction
+            })
+            .collect()
+    }
+}
+
+impl Gene {
+    /// Gene-level interactions: each partner's confidence is the
+    /// expression-weighted sum over the isoforms that bind it.
+    pub fn weighted_interactions(&self, expression: &ExpressionTable) -> Result<Vec<ProteinInteraction>> {
+        let weights = expression.weights(self)?;
+        let mut merged: Vec<ProteinInteraction> = Vec::new();
+
+        for (isoform, weight) in self.isoforms.iter().zip(weights) {
+            for interaction in isoform.interactions(&self.symbol) {
+                let existing = merged.iter_mut().find(|m| {
+                    m.source == interaction.source
+                        && m.target == interaction.target
+                        && m.interaction_type == interaction.interaction_type
+                });
+                match existing {
+                    Some(existing) => existing.confidence += weight * interaction.confidence,
+                    None => merged.push(ProteinInteraction {
+                        confidence: weight * interaction.confidence,
+                        ..interaction
+                    }),
+                }
+            }
+        }
+
+        for interaction in &mut merged {
+            interaction.confidence = interaction.confidence.min(1.0);
+        }
+        Ok(merged)
+    }
+}
+
 impl InteractionNetwork {
     pub fn new() -> Self {
-        Self {
-            nodes: HashSet::new(),
-            edges: Vec::new(),
-            node_attributes: HashMap::new(),
+        Self::default()
+    }
+
+    /// Interns `node`, returning its index; existing nodes keep theirs.
+    pub fn add_node(&mut self, node: &str) -> usize {
+        if let Some(&index) = self.node_index.get(node) {
+            return index;
         }
+        let index = self.node_names.len();
+        self.node_names.push(node.to_string());
+        self.node_index.insert(node.to_string(), index);
+        self.adjacency.push(Vec::new());
+        index
     }
 
     pub fn add_interaction(&mut self, interaction: ProteinInteraction) {
-        self.nodes.insert(interaction.source.clone());
-        self.nodes.insert(interaction.target.clone());
+        let source = self.add_node(&interaction.source);
+        let target = self.add_node(&interaction.target);
+        let edge = self.edges.len();
+        self.adjacency[source].push((target, edge));
+        if source != target {
+            self.adjacency[target].push((source, edge));
+        }
         self.edges.push(interaction);
     }
 
+    pub fn from_edges<'a, I>(edges: I) -> Self
+    where
+        I: IntoIterator<Item = &'a InteractionEdge>,
+    {
+        let mut network = Self::new();
+        for edge in edges {
+            network.add_interaction(edge.into());
+        }
+        network
+    }
+
+    pub fn to_edges(&self) -> Vec<InteractionEdge> {
+        self.edges.iter().map(InteractionEdge::from).collect()
+    }
+
+    /// Edges in insertion order.
+    pub fn edges(&self) -> &[ProteinInteraction] {
+        &self.edges
+    }
+
+    /// Node IDs in the order they were first seen.
+    pub fn nodes(&self) -> &[String] {
+        &self.node_names
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.node_names.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn contains_node(&self, node: &str) -> bool {
+        self.node_index.contains_key(node)
+    }
+
+    /// Interned index of `node`, valid for `node_name` and `incident`.
+    pub fn node_index(&self, node: &str) -> Option<usize> {
+        self.node_index.get(node).copied()
+    }
+
+    pub fn node_name(&self, index: usize) -> &str {
+        &self.node_names[index]
+    }
+
+    /// `(neighbor index, edge)` pairs for the node at `index`.
+    pub fn incident(&self, index: usize) -> impl Iterator<Item = (usize, &ProteinInteraction)> + '_ {
+        self.adjacency[index]
+            .iter()
+            .map(move |&(neighbor, edge)| (neighbor, &self.edges[edge]))
+    }
+
+    /// Per node, its links to other nodes ordered by neighbor index;
+    /// self-loops are dropped.
+    pub(crate) fn links(&self) -> Vec<Vec<Link>> {
+        self.adjacency
+            .iter()
+            .enumerate()
+            .map(|(node, incident)| {
+                let mut links: Vec<Link> = Vec::new();
+                let mut incident: Vec<(usize, f64)> = incident
+                    .iter()
+                    .filter(|&&(neighbor, _)| neighbor != node)
+                    .map(|&(neighbor, edge)| (neighbor, self.edges[edge].confidence))
+                    .collect();
+                incident.sort_by_key(|&(neighbor, _)| neighbor);
+                for (neighbor, confidence) in incident {
+                    match links.last_mut() {
+                        Some(link) if link.node == neighbor => {
+                            link.weight += confidence;
+                            link.confidence = link.confidence.max(confidence);
+                        }
+                        _ => links.push(Link {
+                            node: neighbor,
+                            weight: confidence,
+                            confidence,
+                        }),
+                    }
+                }
+                links
+            })
+            .collect()
+    }
+
+    /// Rewrites every edge's confidence; topology is left untouched.
+    pub fn update_confidence<F>(&mut self, mut confidence: F)
+    where
+        F: FnMut(&ProteinInteraction) -> f64,
+    {
+        for edge in &mut self.edges {
+            edge.confidence = confidence(edge);
+        }
+    }
+
     pub fn add_node_attribute(&mut self, node: &str, key: &str, value: &str) {
         self.node_attributes
             .entry(node.to_string())
-            .or_insert_with(HashMap::new)
+            .or_default()
             .insert(key.to_string(), value.to_string());
     }
 
     pub fn get_neighbors(&self, node: &str) -> Vec<&String> {
-        self.edges
-            .iter()
-            .filter_map(|edge| {
-                if edge.source == *node {
-                    Some(&edge.target)
-                } else if edge.target == *node {
-                    Some(&edge.source)
-                } else {
-                    None
-                }
-            })
-            .collect()
+        match self.node_index(node) {
+            Some(index) => self.adjacency[index]
+                .iter()
+                .map(|&(neighbor, _)| &self.node_names[neighbor])
+                .collect(),
+            None => Vec::new(),
+        }
     }
 
     pub fn get_degree(&self, node: &str) -> usize {
-        self.edges
+        self.node_index(node).map_or(0, |index| self.adjacency[index].len())
+    }
+
+    /// Nodes `node` acts on: targets of its directed edges and partners
+    /// of its undirected ones.
+    pub fn out_neighbors(&self, node: &str) -> Vec<&String> {
+        self.directed_neighbors(node, |edge| edge.leaves(node))
+    }
+
+    /// Nodes acting on `node`.
+    pub fn in_neighbors(&self, node: &str) -> Vec<&String> {
+        self.directed_neighbors(node, |edge| edge.enters(node))
+    }
+
+    pub fn out_degree(&self, node: &str) -> usize {
+        self.out_neighbors(node).len()
+    }
+
+    pub fn in_degree(&self, node: &str) -> usize {
+        self.in_neighbors(node).len()
+    }
+
+    fn directed_neighbors<F>(&self, node: &str, follows: F) -> Vec<&String>
+    where
+        F: Fn(&ProteinInteraction) -> bool,
+    {
+        match self.node_index(node) {
+            Some(index) => self
+                .incident(index)
+                .filter(|(_, edge)| follows(edge))
+                .map(|(neighbor, _)| &self.node_names[neighbor])
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Copy of the network with `node`'s edges replaced by `interactions`.
+    fn with_node_edges(&self, node: &str, interactions: Vec<ProteinInteraction>) -> Self {
+        let mut network = Self {
+            node_attributes: self.node_attributes.clone(),
+            ..Self::new()
+        };
+        for name in &self.node_names {
+            network.add_node(name);
+        }
+        for edge in self.edges.iter().filter(|e| e.source != node && e.target != node) {
+            network.add_interaction(edge.clone());
+        }
+        for interaction in interactions {
+            network.add_interaction(interaction);
+        }
+        network
+    }
+
+    /// The network as seen by one isoform: the gene node keeps only that
+    /// isoform's interactions.
+    pub fn for_isoform(&self, gene: &Gene, isoform: &str) -> Result<Self> {
+        Ok(self.isoform_view(gene, gene.isoform(isoform)?))
+    }
+
+    fn isoform_view(&self, gene: &Gene, isoform: &Isoform) -> Self {
+        let mut network = self.with_node_edges(&gene.symbol, isoform.interactions(&gene.symbol));
+        network.add_node_attribute(&gene.symbol, "isoform", &isoform.name);
+        network
+    }
+
+    /// One network per isoform of `gene`, in isoform order.
+    pub fn isoform_networks(&self, gene: &Gene) -> Vec<(String, Self)> {
+        gene.isoforms
             .iter()
-            .filter(|edge| edge.source == *node || edge.target == *node)
-            .count()
+            .map(|isoform| (isoform.name.clone(), self.isoform_view(gene, isoform)))
+            .collect()
     }
 
+    /// The gene node's edges replaced by expression-weighted isoform edges.
+    pub fn isoform_weighted(&self, gene: &Gene, expression: &ExpressionTable) -> Result<Self> {
+        Ok(self.with_node_edges(&gene.symbol, gene.weighted_interactions(expression)?))
+    }
+
+    /// The seeds' whole connected components; `neighborhood` bounds the
+    /// extraction.
     pub fn get_subnetwork(&self, seed_nodes: &[&str]) -> Self {
         let mut subnetwork = InteractionNetwork::new();
-        let mut visited = HashSet::new();
+        let mut visited = vec![false; self.node_names.len()];
 
-        let mut to_visit: Vec<String> = seed_nodes.iter().map(|s| s.to_string()).collect();
+        // Seeds outside the network keep their attributes but gain no edges
+        for seed in seed_nodes.iter().filter(|seed| !self.contains_node(seed)) {
+            if let Some(attributes) = self.node_attributes.get(*seed) {
+                for (key, value) in attributes {
+                    subnetwork.add_node_attribute(seed, key, value);
+                }
+            }
+        }
+        let mut to_visit: Vec<usize> = seed_nodes.iter().filter_map(|s| self.node_index(s)).collect();
 
         while let Some(node) = to_visit.pop() {
-            if !visited.contains(&node) {
-                visited.insert(node.clone());
+            if visited[node] {
+                continue;
+            }
+            visited[node] = true;
 
-                // Add node attributes if they exist
-                if let Some(attributes) = self.node_attributes.get(&node) {
-                    for (key, value) in attributes {
-                        subnetwork.add_node_attribute(&node, key, value);
-                    }
+            if let Some(attributes) = self.node_attributes.get(&self.node_names[node]) {
+                for (key, value) in attributes {
+                    subnetwork.add_node_attribute(&self.node_names[node], key, value);
                 }
+            }
 
-                // Add edges involving this node
-                for edge in &self.edges {
-                    if edge.source == node || edge.target == node {
-                        subnetwork.add_interaction(edge.clone());
-                        
-                        // Add neighbor to visit list
-                        let neighbor = if edge.source == node {
-                            &edge.target
-                        } else {
-                            &edge.source
-                        };
-                        
-                        if !visited.contains(neighbor) {
-                            to_visit.push(neighbor.clone());
-                        }
-                    }
+            for &(neighbor, edge) in &self.adjacency[node] {
+                // Edges to visited nodes were added when that node was visited
+                if !visited[neighbor] || neighbor == node {
+                    subnetwork.add_interaction(self.edges[edge].clone());
+                }
+                if !visited[neighbor] {
+                    to_visit.push(neighbor);
                 }
             }
         }
 
         subnetwork
     }
-}
\ No newline at end of file
+
+    /// Nodes within `filter.max_depth` hops of any seed over edges the
+    /// filter allows, with every allowed edge among them. Neighbors are
+    /// taken breadth-first, strongest edge first, until `max_nodes` is hit.
+    pub fn neighborhood(&self, seed_nodes: &[&str], filter: &SubnetworkFilter) -> Self {
+        let limit = filter.max_nodes.unwrap_or(usize::MAX);
+        let mut depth: Vec<Option<usize>> = vec![None; self.node_names.len()];
+        let mut selected = Vec::new();
+        let mut queue = VecDeque::new();
+
+        for index in seed_nodes.iter().filter_map(|s| self.node_index(s)) {
+            if depth[index].is_none() && selected.len() < limit {
+                depth[index] = Some(0);
+                selected.push(index);
+                queue.push_back(index);
+            }
+        }
+
+        while let Some(node) = queue.pop_front() {
+            let hops = depth[node].unwrap_or(0);
+            if filter.max_depth.is_some_and(|max| hops >= max) {
+                continue;
+            }
+            let mut next: Vec<(usize, f64)> = self
+                .incident(node)
+                .filter(|(neighbor, edge)| depth[*neighbor].is_none() && filter.allows(edge))
+                .map(|(neighbor, edge)| (neighbor, edge.confidence))
+                .collect();
+            next.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+            for (neighbor, _) in next {
+                if selected.len() >= limit {
+                    break;
+                }
+                if depth[neighbor].is_none() {
+                    depth[neighbor] = Some(hops + 1);
+                    selected.push(neighbor);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        self.induced(&selected, |edge| filter.allows(edge))
+    }
+
+    /// The given nodes, their attributes and every edge between them;
+    /// nodes not in the network are ignored.
+    pub fn induced_subgraph(&self, nodes: &[&str]) -> Self {
+        let indices: Vec<usize> = nodes.iter().filter_map(|n| self.node_index(n)).collect();
+        self.induced(&indices, |_| true)
+    }
+
+    fn induced<F>(&self, indices: &[usize], keep: F) -> Self
+    where
+        F: Fn(&ProteinInteraction) -> bool,
+    {
+        let mut member = vec![false; self.node_names.len()];
+        let mut subgraph = InteractionNetwork::new();
+        for &index in indices {
+            member[index] = true;
+            let name = &self.node_names[index];
+            subgraph.add_node(name);
+            if let Some(attributes) = self.node_attributes.get(name) {
+                subgraph.node_attributes.insert(name.clone(), attributes.clone());
+            }
+        }
+
+        let mut edges: Vec<usize> = indices
+            .iter()
+            .flat_map(|&index| self.adjacency[index].iter())
+            .filter(|&&(neighbor, edge)| member[neighbor] && keep(&self.edges[edge]))
+            .map(|&(_, edge)| edge)
+            .collect();
+        edges.sort_unstable();
+        edges.dedup();
+        for edge in edges {
+            subgraph.add_interaction(self.edges[edge].clone());
+        }
+        subgraph
+    }
+}
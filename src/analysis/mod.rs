@@ -0,0 +1,29 @@
+pub mod alignment;
+pub mod cardiotoxicity_prediction;
+pub mod centrality;
+pub mod communities;
+pub mod interaction_network;
+pub mod motif_scan;
+pub mod paths;
+pub mod pathway_prediction;
+pub mod perturbation;
+pub mod physicochemical;
+pub mod propagation;
+pub mod ptm_rules;
+pub mod randomization;
+pub mod variant_effect;
+
+pub use alignment::{Aligner, AlignmentMode, MultipleAlignment, PairwiseAlignment, SubstitutionMatrix};
+pub use cardiotoxicity_prediction::{CardiotoxicityPredictor, IsoformCardiotoxicity};
+pub use centrality::{CentralityMeasure, CentralityRow, CentralityTable};
+pub use communities::{Communities, CommunityMethod};
+pub use interaction_network::{InteractionNetwork, ProteinInteraction, SubnetworkFilter};
+pub use motif_scan::{MotifCandidate, MotifHit, MotifScanner};
+pub use paths::{NetworkPath, SignedPath};
+pub use pathway_prediction::{PathwayPredictionResult, PathwayPredictor};
+pub use perturbation::{KnockoutImpact, Perturbation, PerturbationAnalysis, PerturbationReport, RankChange};
+pub use physicochemical::{ConsistencyReport, PkaSet, SequenceProperties};
+pub use propagation::{Propagation, ProximityScores};
+pub use ptm_rules::{PtmOutcome, PtmRule, PtmRuleEngine};
+pub use randomization::{PermutationTest, Significance};
+pub use variant_effect::{ProteinVariant, VariantAnnotator, VariantEffect};
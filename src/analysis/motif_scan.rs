@@ -0,0 +1,375 @@
+//! Position-specific motif scanning, with defaults for the BH3 motif
+//!
+//! BH3 motifs are the amphipathic helices that BH3-only proteins insert into
+//! the MCL1 groove. The default matrix is built from BCL-2 family BH3
+//! regions aligned on the conserved h2 leucine; sequences with no matrix hit
+//! fall back to a `LxxxGD`-style consensus pattern.
+
+use std::path::Path;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{MCL1Error, Result};
+use crate::models::interaction::InteractionEdge;
+use crate::models::protein::{Domain, InteractionType, Protein};
+use crate::utils::fasta::open_fasta;
+
+const STANDARD_RESIDUES: &str = "ACDEFGHIKLMNPQRSTVWY";
+
+/// UniProtKB amino acid frequencies, in `STANDARD_RESIDUES` order.
+const BACKGROUND_FREQUENCIES: [f64; 20] = [
+    0.0825, 0.0137, 0.0545, 0.0675, 0.0386, 0.0707, 0.0227, 0.0596, 0.0584, 0.0966, 0.0242, 0.0406, 0.0470,
+    0.0393, 0.0553, 0.0656, 0.0534, 0.0687, 0.0108, 0.0292,
+];
+
+/// BH3 regions of BIM, NOXA, PUMA, BAK, BID, BAD, BAX, MCL1, BIK, HRK, BMF,
+/// BCL2 and BCL-XL; the h2 leucine is at index 4.
+pub const BH3_SEEDS: &[&str] = &[
+    "IAQELRRIGDEF",
+    "CATQLRRFGDKL",
+    "IGAQLRRMADDL",
+    "VGRQLAIIGDDI",
+    "IARHLAQVGDSM",
+    "YGRELRRMSDEF",
+    "LSECLKRIGDEL",
+    "ALETLRRVGDGV",
+    "LALRLACIGDEM",
+    "TAARLKALGDEL",
+    "IARKLQCIADQF",
+    "VHLTLRQAGDDF",
+    "VKQALREAGDEF",
+];
+
+/// Core BH3 consensus: h2 hydrophobic, three residues, a small residue, the
+/// invariant aspartate and the h3 hydrophobic.
+pub const BH3_CONSENSUS: &str = "[LIVMF].{3}[GAS]D.[LIVMF]";
+
+/// Position of the consensus match within a BH3 matrix window.
+const BH3_CONSENSUS_OFFSET: usize = 4;
+
+/// Minimum log2-odds score for a BH3 matrix hit.
+pub const DEFAULT_BH3_THRESHOLD: f64 = 12.0;
+
+const BH3_PSEUDOCOUNT: f64 = 0.5;
+
+/// Log2-odds position-specific scoring matrix.
+#[derive(Debug, Clone)]
+pub struct Pssm {
+    columns: Vec<[f64; 20]>,
+}
+
+fn residue_index(residue: u8) -> Option<usize> {
+    STANDARD_RESIDUES.find(residue.to_ascii_uppercase() as char)
+}
+
+/// One byte per residue, with non-ASCII characters replaced by an
+/// unscored placeholder so byte offsets stay residue positions.
+fn residue_bytes(sequence: &[char]) -> Vec<u8> {
+    sequence.iter().map(|&c| if c.is_ascii() { c as u8 } else { b'?' }).collect()
+}
+
+impl Pssm {
+    /// Builds a matrix from equal-length aligned sequences, spreading
+    /// `pseudocount` over the background frequencies.
+    pub fn from_alignment(sequences: &[&str], pseudocount: f64) -> Result<Self> {
+        let width = sequences
+            .first()
+            .map(|s| s.len())
+            .filter(|width| *width > 0)
+            .ok_or_else(|| MCL1Error::parse("alignment", 0, 0, "no sequences to build a matrix from"))?;
+
+        let mut counts = vec![[0.0; 20]; width];
+        for (line, sequence) in sequences.iter().enumerate() {
+            if sequence.len() != width {
+                return Err(MCL1Error::parse(
+                    "alignment",
+                    line + 1,
+                    0,
+                    format!("expected {} residues, found {}", width, sequence.len()),
+                ));
+            }
+            for (column, residue) in sequence.bytes().enumerate() {
+                match residue_index(residue) {
+                    Some(index) => counts[column][index] += 1.0,
+                    None => {
+                        return Err(MCL1Error::parse(
+                            "alignment",
+                            line + 1,
+                            column + 1,
+                            format!("non-standard residue '{}'", residue as char),
+                        ))
+                    }
+                }
+            }
+        }
+
+        let total = sequences.len() as f64 + pseudocount;
+        let columns = counts
+            .into_iter()
+            .map(|column| {
+                let mut scores = [0.0; 20];
+                for (index, score) in scores.iter_mut().enumerate() {
+                    let background = BACKGROUND_FREQUENCIES[index];
+                    let frequency = (column[index] + pseudocount * background) / total;
+                    *score = (frequency / background).log2();
+                }
+                scores
+            })
+            .collect();
+        Ok(Self { columns })
+    }
+
+    pub fn bh3() -> Self {
+        Self::from_alignment(BH3_SEEDS, BH3_PSEUDOCOUNT).expect("BH3 seeds are aligned")
+    }
+
+    pub fn width(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Highest score any window can reach.
+    pub fn max_score(&self) -> f64 {
+        self.columns
+            .iter()
+            .map(|column| column.iter().cloned().fold(f64::MIN, f64::max))
+            .sum()
+    }
+
+    /// Scores the window starting at `start`, which may run off either end
+    /// of `sequence`; missing and non-standard residues score zero.
+    fn score_at(&self, sequence: &[u8], start: isize) -> f64 {
+        self.columns
+            .iter()
+            .enumerate()
+            .filter_map(|(offset, column)| {
+                let position = usize::try_from(start + offset as isize).ok()?;
+                residue_index(*sequence.get(position)?).map(|index| column[index])
+            })
+            .sum()
+    }
+
+    /// Scores a window of exactly `width()` residues.
+    pub fn score(&self, window: &str) -> Option<f64> {
+        let residues = residue_bytes(&window.chars().collect::<Vec<_>>());
+        (residues.len() == self.width()).then(|| self.score_at(&residues, 0))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScanMethod {
+    Pssm,
+    Consensus,
+}
+
+/// One motif occurrence; `start` and `end` are 1-based and inclusive like
+/// `Domain` positions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MotifHit {
+    pub start: usize,
+    pub end: usize,
+    pub sequence: String,
+    pub score: f64,
+    pub method: ScanMethod,
+}
+
+impl MotifHit {
+    pub fn to_domain(&self, motif: &str) -> Domain {
+        let method = match self.method {
+            ScanMethod::Pssm => "PSSM",
+            ScanMethod::Consensus => "consensus",
+        };
+        Domain {
+            id: format!("{}:{}-{}", motif, self.start, self.end),
+            name: motif.to_string(),
+            start: self.start,
+            end: self.end,
+            description: format!("{} motif ({})", motif, method),
+            score: Some(self.score),
+        }
+    }
+
+    fn overlaps(&self, other: &MotifHit) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+}
+
+/// A scanned protein together with its motif hits.
+#[derive(Debug, Clone)]
+pub struct MotifCandidate {
+    pub protein: Protein,
+    pub hits: Vec<MotifHit>,
+}
+
+impl MotifCandidate {
+    pub fn best_score(&self) -> f64 {
+        self.hits.iter().map(|hit| hit.score).fold(f64::MIN, f64::max)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MotifScanner {
+    /// Motif name used for domains, e.g. `"BH3"`.
+    pub motif: String,
+    pub pssm: Pssm,
+    pub threshold: f64,
+    /// Fallback pattern and the offset of its match within a matrix window.
+    pub consensus: Option<(Regex, usize)>,
+}
+
+impl Default for MotifScanner {
+    fn default() -> Self {
+        Self::bh3()
+    }
+}
+
+impl MotifScanner {
+    pub fn new(motif: &str, pssm: Pssm, threshold: f64) -> Self {
+        Self {
+            motif: motif.to_string(),
+            pssm,
+            threshold,
+            consensus: None,
+        }
+    }
+
+    /// BH3 matrix with the `LxxxGD` consensus fallback.
+    pub fn bh3() -> Self {
+        Self::new("BH3", Pssm::bh3(), DEFAULT_BH3_THRESHOLD)
+            .with_consensus(BH3_CONSENSUS, BH3_CONSENSUS_OFFSET)
+            .expect("BH3 consensus is a valid pattern")
+    }
+
+    pub fn with_threshold(mut self, threshold: f64) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Reports matches of `pattern` in sequences without any matrix hit;
+    /// `offset` is where the match starts within a matrix window.
+    pub fn with_consensus(mut self, pattern: &str, offset: usize) -> Result<Self> {
+        let regex = Regex::new(pattern).map_err(|e| MCL1Error::parse("consensus pattern", 1, 0, e.to_string()))?;
+        self.consensus = Some((regex, offset));
+        Ok(self)
+    }
+
+    pub fn without_consensus(mut self) -> Self {
+        self.consensus = None;
+        self
+    }
+
+    /// Finds motifs in `sequence`, keeping the best of any overlapping
+    /// matrix hits, ordered by position. Consensus matches are only reported
+    /// when the matrix finds nothing.
+    pub fn scan_sequence(&self, sequence: &str) -> Vec<MotifHit> {
+        let sequence: Vec<char> = sequence.chars().map(|c| c.to_ascii_uppercase()).collect();
+        let residues = residue_bytes(&sequence);
+        let window = |start: usize, end: usize| sequence[start..end].iter().collect::<String>();
+        let width = self.pssm.width();
+
+        let mut candidates: Vec<MotifHit> = (0..residues.len().saturating_sub(width - 1))
+            .filter_map(|start| {
+                let score = self.pssm.score_at(&residues, start as isize);
+                (score >= self.threshold).then(|| MotifHit {
+                    start: start + 1,
+                    end: start + width,
+                    sequence: window(start, start + width),
+                    score,
+                    method: ScanMethod::Pssm,
+                })
+            })
+            .collect();
+        candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+        let mut hits: Vec<MotifHit> = Vec::new();
+        for candidate in candidates {
+            if !hits.iter().any(|hit| hit.overlaps(&candidate)) {
+                hits.push(candidate);
+            }
+        }
+
+        if let Some((regex, offset)) = self.consensus.as_ref().filter(|_| hits.is_empty()) {
+            let haystack: String = residues.iter().map(|&b| b as char).collect();
+            for found in regex.find_iter(&haystack) {
+                let window_start = found.start() as isize - *offset as isize;
+                let start = window_start.max(0) as usize;
+                let end = ((window_start + width as isize) as usize).min(residues.len());
+                hits.push(MotifHit {
+                    start: start + 1,
+                    end,
+                    sequence: window(start, end),
+                    score: self.pssm.score_at(&residues, window_start),
+                    method: ScanMethod::Consensus,
+                });
+            }
+        }
+
+        hits.sort_by_key(|hit| hit.start);
+        hits
+    }
+
+    pub fn scan_protein(&self, protein: &Protein) -> Vec<MotifHit> {
+        self.scan_sequence(&protein.sequence)
+    }
+
+    /// Adds a scored domain to `protein` for every hit, returning the hit count.
+    pub fn annotate(&self, protein: &mut Protein) -> usize {
+        let hits = self.scan_protein(protein);
+        for hit in &hits {
+            protein.add_domain(hit.to_domain(&self.motif));
+        }
+        hits.len()
+    }
+
+    /// Annotates `protein` and keeps it as a candidate if it has any hits.
+    fn candidate(&self, mut protein: Protein) -> Option<MotifCandidate> {
+        let hits = self.scan_protein(&protein);
+        if hits.is_empty() {
+            return None;
+        }
+        for hit in &hits {
+            protein.add_domain(hit.to_domain(&self.motif));
+        }
+        Some(MotifCandidate { protein, hits })
+    }
+
+    /// Annotates each protein and returns those with hits, best score first.
+    pub fn rank<I>(&self, proteins: I) -> Vec<MotifCandidate>
+    where
+        I: IntoIterator<Item = Protein>,
+    {
+        let mut candidates: Vec<MotifCandidate> =
+            proteins.into_iter().filter_map(|protein| self.candidate(protein)).collect();
+        candidates.sort_by(|a, b| b.best_score().total_cmp(&a.best_score()));
+        candidates
+    }
+
+    /// As `rank`, over a (possibly gzipped) proteome FASTA file that is
+    /// scanned one record at a time, so only candidates are kept in memory.
+    pub fn scan_fasta<P: AsRef<Path>>(&self, path: P) -> Result<Vec<MotifCandidate>> {
+        let mut candidates = Vec::new();
+        for protein in open_fasta(path)? {
+            candidates.extend(self.candidate(protein?));
+        }
+        candidates.sort_by(|a, b| b.best_score().total_cmp(&a.best_score()));
+        Ok(candidates)
+    }
+
+    /// Proposes a binding edge from `receptor` to every candidate other than
+    /// the receptor itself, with confidence from the best hit relative to the
+    /// matrix maximum.
+    pub fn binding_hypotheses(&self, receptor: &str, candidates: &[MotifCandidate]) -> Vec<InteractionEdge> {
+        let max_score = self.pssm.max_score();
+        candidates
+            .iter()
+            .filter(|candidate| candidate.protein.id != receptor && candidate.protein.name != receptor)
+            .map(|candidate| {
+                let confidence = (candidate.best_score() / max_score).clamp(0.0, 1.0);
+                let mut edge = InteractionEdge::new(receptor, &candidate.protein.name, InteractionType::Binding, confidence)
+                    .with_evidence(&format!("{} motif scan", self.motif));
+                edge.target_name = Some(candidate.protein.name.clone());
+                edge
+            })
+            .collect()
+    }
+}
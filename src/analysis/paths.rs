@@ -0,0 +1,411 @@
+//! Weighted paths through `InteractionNetwork`
+//!
+//! A link costs `-ln(confidence)`, using the most confident of any parallel
+//! edges, so a path's total cost is the negative log of the product of its
+//! confidences. The cheapest path is therefore the most probable chain of
+//! interactions. Zero-confidence links are never traversed.
+//!
+//! Signed searches follow edge direction and only use activating or
+//! inhibiting edges, and report the net sign of each chain.
+
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+
+use crate::analysis::interaction_network::{Cost, InteractionNetwork, Link, ProteinInteraction};
+use crate::error::{MCL1Error, Result};
+use crate::models::interaction::Sign;
+
+/// A simple path and the product of its edge confidences.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NetworkPath {
+    pub nodes: Vec<String>,
+    /// Sum of `-ln(confidence)` over the path's links.
+    pub cost: f64,
+    pub confidence: f64,
+}
+
+impl NetworkPath {
+    /// Number of links on the path.
+    pub fn hops(&self) -> usize {
+        self.nodes.len().saturating_sub(1)
+    }
+}
+
+/// A directed chain of signed edges and its net effect on the last node.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignedPath {
+    pub nodes: Vec<String>,
+    pub cost: f64,
+    pub confidence: f64,
+    /// Product of the edge signs: `Negative` if the source ends up
+    /// inhibiting the target.
+    pub sign: Sign,
+}
+
+fn edge_cost(edge: &ProteinInteraction) -> f64 {
+    -edge.confidence.min(1.0).ln()
+}
+
+fn link_cost(link: &Link) -> f64 {
+    -link.confidence.min(1.0).ln()
+}
+
+/// Links traversable by path searches: positive confidence, no self-loops.
+fn usable(links: &[Link]) -> impl Iterator<Item = &Link> {
+    links.iter().filter(|link| link.confidence > 0.0)
+}
+
+fn cost_between(links: &[Vec<Link>], a: usize, b: usize) -> f64 {
+    links[a]
+        .binary_search_by_key(&b, |link| link.node)
+        .map(|i| link_cost(&links[a][i]))
+        .unwrap_or(f64::INFINITY)
+}
+
+fn path_cost(links: &[Vec<Link>], path: &[usize]) -> f64 {
+    path.windows(2).map(|pair| cost_between(links, pair[0], pair[1])).sum()
+}
+
+/// Nodes and links excluded from a search, as used by Yen's algorithm.
+struct Exclusions {
+    nodes: Vec<bool>,
+    links: HashSet<(usize, usize)>,
+}
+
+impl Exclusions {
+    fn none(n: usize) -> Self {
+        Self {
+            nodes: vec![false; n],
+            links: HashSet::new(),
+        }
+    }
+
+    fn allows(&self, from: usize, to: usize) -> bool {
+        !self.nodes[to] && !self.links.contains(&(from, to))
+    }
+}
+
+/// A* search from any of `sources` to the nearest node accepted by
+/// `is_target`; `heuristic` must never overestimate the remaining cost, and
+/// a zero heuristic makes this Dijkstra's algorithm. Nodes are expanded
+/// again when a cheaper route to them turns up, so the heuristic need not
+/// be consistent.
+fn search<T, H>(
+    links: &[Vec<Link>],
+    sources: &[usize],
+    is_target: T,
+    heuristic: H,
+    excluded: &Exclusions,
+) -> Option<Vec<usize>>
+where
+    T: Fn(usize) -> bool,
+    H: Fn(usize) -> f64,
+{
+    let n = links.len();
+    let mut distance = vec![f64::INFINITY; n];
+    let mut previous: Vec<Option<usize>> = vec![None; n];
+    let mut heap = BinaryHeap::new();
+
+    for &source in sources {
+        distance[source] = 0.0;
+        heap.push(Reverse((Cost(heuristic(source)), source, Cost(0.0))));
+    }
+    while let Some(Reverse((_, v, Cost(reached)))) = heap.pop() {
+        if reached > distance[v] {
+            continue;
+        }
+        if is_target(v) {
+            let mut path = vec![v];
+            let mut node = v;
+            while let Some(p) = previous[node] {
+                path.push(p);
+                node = p;
+            }
+            path.reverse();
+            return Some(path);
+        }
+        for link in usable(&links[v]).filter(|link| excluded.allows(v, link.node)) {
+            let candidate = distance[v] + link_cost(link);
+            if candidate < distance[link.node] {
+                distance[link.node] = candidate;
+                previous[link.node] = Some(v);
+                heap.push(Reverse((Cost(candidate + heuristic(link.node)), link.node, Cost(candidate))));
+            }
+        }
+    }
+    None
+}
+
+impl InteractionNetwork {
+    fn endpoints(&self, source: &str, target: &str) -> Result<(usize, usize)> {
+        let index = |node: &str| self.node_index(node).ok_or_else(|| MCL1Error::UnknownProtein(node.to_string()));
+        Ok((index(source)?, index(target)?))
+    }
+
+    fn to_path(&self, links: &[Vec<Link>], path: &[usize]) -> NetworkPath {
+        let cost = path_cost(links, path);
+        NetworkPath {
+            nodes: path.iter().map(|&i| self.node_name(i).to_string()).collect(),
+            cost,
+            confidence: (-cost).exp(),
+        }
+    }
+
+    /// Most confident path from `source` to `target` (Dijkstra), or `None`
+    /// if they are not connected.
+    pub fn shortest_path(&self, source: &str, target: &str) -> Result<Option<NetworkPath>> {
+        self.shortest_path_astar(source, target, |_| 0.0)
+    }
+
+    /// As `shortest_path`, guided by `heuristic`, a lower bound on the cost
+    /// from a node to `target`; it need not be consistent. Overestimating
+    /// heuristics may miss the best path.
+    pub fn shortest_path_astar<H>(&self, source: &str, target: &str, heuristic: H) -> Result<Option<NetworkPath>>
+    where
+        H: Fn(&str) -> f64,
+    {
+        let (s, t) = self.endpoints(source, target)?;
+        let links = self.links();
+        let path = search(&links, &[s], |v| v == t, |v| heuristic(self.node_name(v)), &Exclusions::none(links.len()));
+        Ok(path.map(|path| self.to_path(&links, &path)))
+    }
+
+    /// Up to `k` loopless paths in order of decreasing confidence (Yen's
+    /// algorithm).
+    pub fn k_shortest_paths(&self, source: &str, target: &str, k: usize) -> Result<Vec<NetworkPath>> {
+        let (s, t) = self.endpoints(source, target)?;
+        let links = self.links();
+        let n = links.len();
+
+        let mut accepted: Vec<Vec<usize>> = Vec::new();
+        match search(&links, &[s], |v| v == t, |_| 0.0, &Exclusions::none(n)) {
+            Some(path) if k > 0 => accepted.push(path),
+            _ => return Ok(Vec::new()),
+        }
+        let mut candidates: Vec<(f64, Vec<usize>)> = Vec::new();
+
+        while accepted.len() < k {
+            let previous = accepted.last().cloned().unwrap_or_default();
+            for i in 0..previous.len() - 1 {
+                let root = &previous[..=i];
+                let mut excluded = Exclusions::none(n);
+                for path in accepted.iter().filter(|path| path.len() > i + 1 && path[..=i] == *root) {
+                    excluded.links.insert((path[i], path[i + 1]));
+                }
+                for &node in &root[..i] {
+                    excluded.nodes[node] = true;
+                }
+
+                if let Some(spur) = search(&links, &[previous[i]], |v| v == t, |_| 0.0, &excluded) {
+                    let mut path = root[..i].to_vec();
+                    path.extend(spur);
+                    if !accepted.contains(&path) && !candidates.iter().any(|(_, c)| *c == path) {
+                        candidates.push((path_cost(&links, &path), path));
+                    }
+                }
+            }
+
+            let Some(best) = candidates
+                .iter()
+                .enumerate()
+                .min_by(|a, b| a.1 .0.total_cmp(&b.1 .0))
+                .map(|(i, _)| i)
+            else {
+                break;
+            };
+            accepted.push(candidates.swap_remove(best).1);
+        }
+
+        Ok(accepted.iter().map(|path| self.to_path(&links, path)).collect())
+    }
+
+    /// Most confident path from any node in `from` to any node in `to`;
+    /// nodes missing from the network are ignored.
+    pub fn best_path_between(&self, from: &[&str], to: &[&str]) -> Option<NetworkPath> {
+        let links = self.links();
+        let sources: Vec<usize> = from.iter().filter_map(|node| self.node_index(node)).collect();
+        let mut is_target = vec![false; links.len()];
+        for index in to.iter().filter_map(|node| self.node_index(node)) {
+            is_target[index] = true;
+        }
+        let path = search(&links, &sources, |v| is_target[v], |_| 0.0, &Exclusions::none(links.len()))?;
+        Some(self.to_path(&links, &path))
+    }
+
+    /// Every simple path of at most `max_hops` links, most confident first.
+    /// The count grows exponentially with `max_hops` on dense networks.
+    pub fn all_paths(&self, source: &str, target: &str, max_hops: usize) -> Result<Vec<NetworkPath>> {
+        let (s, t) = self.endpoints(source, target)?;
+        let links = self.links();
+        let mut on_path = vec![false; links.len()];
+        let mut path = vec![s];
+        let mut found = Vec::new();
+        on_path[s] = true;
+        enumerate_paths(&links, t, max_hops, &mut path, &mut on_path, &mut found);
+
+        let mut paths: Vec<NetworkPath> = found.iter().map(|p| self.to_path(&links, p)).collect();
+        paths.sort_by(|a, b| a.cost.total_cmp(&b.cost).then_with(|| a.hops().cmp(&b.hops())));
+        Ok(paths)
+    }
+}
+
+fn enumerate_paths(
+    links: &[Vec<Link>],
+    target: usize,
+    max_hops: usize,
+    path: &mut Vec<usize>,
+    on_path: &mut [bool],
+    found: &mut Vec<Vec<usize>>,
+) {
+    let Some(&v) = path.last() else { return };
+    if v == target {
+        if path.len() > 1 {
+            found.push(path.clone());
+        }
+        return;
+    }
+    if path.len() > max_hops {
+        return;
+    }
+    for link in usable(&links[v]) {
+        if !on_path[link.node] {
+            on_path[link.node] = true;
+            path.push(link.node);
+            enumerate_paths(links, target, max_hops, path, on_path, found);
+            path.pop();
+            on_path[link.node] = false;
+        }
+    }
+}
+
+impl InteractionNetwork {
+    /// Signed edges that can be followed out of the node at `index`, with
+    /// the node they lead to.
+    fn signed_steps(&self, index: usize) -> impl Iterator<Item = (usize, &ProteinInteraction)> + '_ {
+        let name = self.node_name(index);
+        self.incident(index)
+            .filter(move |(_, edge)| edge.confidence > 0.0 && edge.sign() != Sign::Unsigned && edge.leaves(name))
+    }
+
+    /// Every directed chain of signed edges from `source` to `target` of at
+    /// most `max_hops` steps with no repeated node, most confident first.
+    pub fn signed_paths(&self, source: &str, target: &str, max_hops: usize) -> Result<Vec<SignedPath>> {
+        let (s, t) = self.endpoints(source, target)?;
+        let mut on_path = vec![false; self.node_count()];
+        let mut paths = Vec::new();
+        on_path[s] = true;
+        self.enumerate_signed(t, max_hops, &mut vec![(s, 0.0, Sign::Positive)], &mut on_path, &mut paths);
+        paths.sort_by(|a, b| a.cost.total_cmp(&b.cost).then_with(|| a.nodes.len().cmp(&b.nodes.len())));
+        Ok(paths)
+    }
+
+    fn enumerate_signed(
+        &self,
+        target: usize,
+        max_hops: usize,
+        path: &mut Vec<(usize, f64, Sign)>,
+        on_path: &mut [bool],
+        found: &mut Vec<SignedPath>,
+    ) {
+        let Some(&(v, cost, sign)) = path.last() else { return };
+        if v == target && path.len() > 1 {
+            found.push(SignedPath {
+                nodes: path.iter().map(|&(i, _, _)| self.node_name(i).to_string()).collect(),
+                cost,
+                confidence: (-cost).exp(),
+                sign,
+            });
+            return;
+        }
+        if path.len() > max_hops {
+            return;
+        }
+        for (next, edge) in self.signed_steps(v) {
+            if !on_path[next] {
+                on_path[next] = true;
+                path.push((next, cost + edge_cost(edge), sign.then(edge.sign())));
+                self.enumerate_signed(target, max_hops, path, on_path, found);
+                path.pop();
+                on_path[next] = false;
+            }
+        }
+    }
+
+    /// Most confident directed chain of signed edges whose net effect is
+    /// `sign`. A chain may pass a node twice when only a feedback loop
+    /// gives the requested sign, and when `source` is `target` the result is
+    /// the best feedback loop through it; `Unsigned` never matches.
+    pub fn shortest_signed_path(&self, source: &str, target: &str, sign: Sign) -> Result<Option<SignedPath>> {
+        let (s, t) = self.endpoints(source, target)?;
+        let parity = |sign: Sign| usize::from(sign == Sign::Negative);
+        if sign == Sign::Unsigned {
+            return Ok(None);
+        }
+
+        // Search over (node, net sign so far) states, starting from the first
+        // steps out of `source` so that a loop back to it is a path of its
+        // own rather than the empty one. States with no `previous` were
+        // entered straight from `source`.
+        let n = self.node_count();
+        let mut distance = vec![[f64::INFINITY; 2]; n];
+        let mut previous: Vec<[Option<(usize, usize)>; 2]> = vec![[None; 2]; n];
+        let mut heap = BinaryHeap::new();
+        for (next, edge) in self.signed_steps(s) {
+            let q = parity(edge.sign());
+            let cost = edge_cost(edge);
+            if cost < distance[next][q] {
+                distance[next][q] = cost;
+                heap.push(Reverse((Cost(cost), next, q)));
+            }
+        }
+        let goal = parity(sign);
+        while let Some(Reverse((Cost(d), v, p))) = heap.pop() {
+            if d > distance[v][p] {
+                continue;
+            }
+            if v == t && p == goal {
+                break;
+            }
+            for (next, edge) in self.signed_steps(v) {
+                let q = p ^ parity(edge.sign());
+                let candidate = d + edge_cost(edge);
+                if candidate < distance[next][q] {
+                    distance[next][q] = candidate;
+                    previous[next][q] = Some((v, p));
+                    heap.push(Reverse((Cost(candidate), next, q)));
+                }
+            }
+        }
+
+        if distance[t][goal].is_infinite() {
+            return Ok(None);
+        }
+        let mut nodes = vec![t];
+        let mut state = (t, goal);
+        while let Some(prior) = previous[state.0][state.1] {
+            nodes.push(prior.0);
+            state = prior;
+        }
+        nodes.push(s);
+        nodes.reverse();
+        let cost = distance[t][goal];
+        Ok(Some(SignedPath {
+            nodes: nodes.iter().map(|&i| self.node_name(i).to_string()).collect(),
+            cost,
+            confidence: (-cost).exp(),
+            sign,
+        }))
+    }
+
+    /// The most confident signed chain from `source` to `target`, whose
+    /// `sign` is the net effect of one on the other.
+    pub fn net_effect(&self, source: &str, target: &str) -> Result<Option<SignedPath>> {
+        let positive = self.shortest_signed_path(source, target, Sign::Positive)?;
+        let negative = self.shortest_signed_path(source, target, Sign::Negative)?;
+        Ok(match (positive, negative) {
+            (Some(p), Some(n)) => Some(if n.cost < p.cost { n } else { p }),
+            (p, n) => p.or(n),
+        })
+    }
+}
@@ -1,13 +1,10 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct MetabolicPathway {
-    pub id: String,
-    pub name: String,
-    pub activation_score: f64,
-    pub associated_proteins: Vec<String>,
-}
+use crate::error::{MCL1Error, Result};
+use crate::models::metabolic_pathway::{MetabolicPathway, PathwayActivation, PathwayCollection};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PathwayPredictionResult {
@@ -16,10 +13,53 @@ pub struct PathwayPredictionResult {
     pub prediction_timestamp: String,
 }
 
+impl PathwayPredictionResult {
+    /// Applies the prediction: predicted pathways become active and take the
+    /// predicted score, pathways missing from it fall below threshold and
+    /// become inactive. Unknown predicted pathways are added.
+    pub fn apply_to(&self, collection: &mut PathwayCollection) {
+        for pathway in collection.pathways.values_mut() {
+            pathway.set_activation_state(PathwayActivation::Inactive);
+        }
+
+        for predicted in &self.predicted_pathways {
+            match collection.get_mut_pathway(&predicted.id) {
+                Some(pathway) => {
+                    pathway.set_activation_state(predicted.activation_state);
+                    pathway.mcl1_interaction_score = predicted.mcl1_interaction_score;
+                    pathway.associated_proteins = predicted.associated_proteins.clone();
+                }
+                None => collection.add_pathway(predicted.clone()),
+            }
+        }
+    }
+}
+
+/// Input document for a standalone pathway prediction run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PathwayPredictionInput {
+    pub mcl1_interactions: Vec<String>,
+    pub metabolic_data: HashMap<String, f64>,
+}
+
+impl PathwayPredictionInput {
+    pub fn from_json_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let file = path.as_ref().display().to_string();
+        let text = fs::read_to_string(&path)?;
+        serde_json::from_str(&text).map_err(|e| MCL1Error::from_json(&file, e))
+    }
+}
+
 pub struct PathwayPredictor {
     model_parameters: HashMap<String, f64>,
 }
 
+impl Default for PathwayPredictor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl PathwayPredictor {
     pub fn new() -> Self {
         let mut params = HashMap::new();
@@ -32,23 +72,46 @@ impl PathwayPredictor {
         }
     }
 
+    pub fn with_parameters(model_parameters: HashMap<String, f64>) -> Self {
+        PathwayPredictor { model_parameters }
+    }
+
+    pub fn parameter(&self, name: &str) -> Result<f64> {
+        self.model_parameters
+            .get(name)
+            .copied()
+            .ok_or_else(|| MCL1Error::MissingParameter(name.to_string()))
+    }
+
+    pub fn set_parameter(&mut self, name: &str, value: f64) {
+        self.model_parameters.insert(name.to_string(), value);
+    }
+
     pub fn predict_pathways(
         &self,
         mcl1_interactions: &[String],
         metabolic_data: &HashMap<String, f64>,
-    ) -> PathwayPredictionResult {
+    ) -> Result<PathwayPredictionResult> {
+        let threshold = self.parameter("mcl1_threshold")?;
         let mut pathways = Vec::new();
         let mut total_confidence = 0.0;
         
         // Simulate pathway prediction logic
         for (pathway_id, &score) in metabolic_data {
-            if score > self.model_parameters["mcl1_threshold"] {
-                let pathway = MetabolicPathway {
-                    id: pathway_id.clone(),
-                    name: format!("Pathway_{}", pathway_id),
-                    activation_score: score,
-                    associated_proteins: vec!["MCL1".to_string(), "mTOR".to_string()],
-                };
+            MCL1Error::check_range(pathway_id, score, 0.0, 1.0)?;
+            if score > threshold {
+                let mut associated_proteins = vec!["MCL1".to_string()];
+                associated_proteins.extend(mcl1_interactions.iter().cloned());
+
+                let mut pathway = MetabolicPathway::new(
+                    pathway_id.clone(),
+                    format!("Pathway_{}", pathway_id),
+                    "Predicted from MCL1 interaction data".to_string(),
+                    Vec::new(),
+                    score,
+                );
+                pathway.set_activation_state(PathwayActivation::Active);
+                pathway.associated_proteins = associated_proteins;
                 
                 pathways.push(pathway);
                 total_confidence += score;
@@ -61,10 +124,10 @@ impl PathwayPredictor {
             0.0
         };
         
-        PathwayPredictionResult {
+        Ok(PathwayPredictionResult {
             predicted_pathways: pathways,
             confidence_score: avg_confidence,
             prediction_timestamp: chrono::Utc::now().to_rfc3339(),
-        }
+        })
     }
 }
\ No newline at end of file
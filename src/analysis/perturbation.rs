@@ -0,0 +1,347 @@
+//! In-silico knockout and perturbation simulation on `InteractionNetwork`
+//!
+//! A perturbation removes nodes, removes edges or scales down a node's edge
+//! confidences, and is scored against the unperturbed network on three
+//! readouts: pairwise connectivity (node pairs that can still reach each
+//! other), the most confident path between the apoptosis and metabolism
+//! modules, and centrality rankings. `knockout_screen` knocks out every
+//! node in turn and ranks nodes by impact; it skips the centrality readout,
+//! which dominates the cost on large networks.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+use crate::analysis::centrality::{CentralityMeasure, CentralityTable};
+use crate::analysis::interaction_network::InteractionNetwork;
+use crate::analysis::paths::NetworkPath;
+use crate::error::{MCL1Error, Result};
+use crate::models::metabolic_pathway::METABOLIC_GENE_SETS;
+
+/// BCL-2 family members and the caspases downstream of them.
+pub const APOPTOSIS_GENES: &[&str] = &[
+    "MCL1", "BCL2", "BCL2L1", "BAK1", "BAX", "BCL2L11", "PMAIP1", "BBC3", "BID", "CYCS", "CASP9", "CASP3",
+];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Perturbation {
+    /// Removes the nodes and every edge touching them.
+    KnockOut(Vec<String>),
+    /// Removes every edge between each pair, in either orientation.
+    RemoveEdges(Vec<(String, String)>),
+    /// Multiplies the confidence of the node's edges by `residual`, in `[0, 1]`.
+    Inhibit { node: String, residual: f64 },
+}
+
+impl Perturbation {
+    pub fn knock_out(nodes: &[&str]) -> Self {
+        Perturbation::KnockOut(nodes.iter().map(|n| n.to_string()).collect())
+    }
+
+    pub fn remove_edges(pairs: &[(&str, &str)]) -> Self {
+        Perturbation::RemoveEdges(pairs.iter().map(|(a, b)| (a.to_string(), b.to_string())).collect())
+    }
+
+    pub fn inhibit(node: &str, residual: f64) -> Self {
+        Perturbation::Inhibit {
+            node: node.to_string(),
+            residual,
+        }
+    }
+
+    /// The perturbed copy of `network`; naming a node the network does not
+    /// contain is an `UnknownProtein` error.
+    pub fn apply(&self, network: &InteractionNetwork) -> Result<InteractionNetwork> {
+        let known = |node: &str| {
+            if network.contains_node(node) {
+                Ok(())
+            } else {
+                Err(MCL1Error::UnknownProtein(node.to_string()))
+            }
+        };
+        match self {
+            Perturbation::KnockOut(nodes) => {
+                nodes.iter().try_for_each(|node| known(node))?;
+                let nodes: Vec<&str> = nodes.iter().map(String::as_str).collect();
+                Ok(network.without_nodes(&nodes))
+            }
+            Perturbation::RemoveEdges(pairs) => {
+                pairs.iter().try_for_each(|(a, b)| known(a).and_then(|_| known(b)))?;
+                let pairs: Vec<(&str, &str)> = pairs.iter().map(|(a, b)| (a.as_str(), b.as_str())).collect();
+                Ok(network.without_edges(&pairs))
+            }
+            Perturbation::Inhibit { node, residual } => network.inhibited(node, *residual),
+        }
+    }
+}
+
+/// How one node's centrality rank moved under a perturbation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RankChange {
+    pub node: String,
+    /// 1-based rank in the unperturbed network.
+    pub rank_before: usize,
+    /// `None` if the node was removed.
+    pub rank_after: Option<usize>,
+    pub score_before: f64,
+    pub score_after: Option<f64>,
+}
+
+impl RankChange {
+    /// Places gained (positive) or lost (negative); `None` if removed.
+    pub fn shift(&self) -> Option<i64> {
+        self.rank_after.map(|after| self.rank_before as i64 - after as i64)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PerturbationReport {
+    pub perturbation: Perturbation,
+    pub components_before: usize,
+    pub components_after: usize,
+    pub largest_component_before: usize,
+    pub largest_component_after: usize,
+    /// Fraction of connected node pairs that are no longer connected.
+    pub connectivity_loss: f64,
+    pub module_path_before: Option<NetworkPath>,
+    pub module_path_after: Option<NetworkPath>,
+    pub measure: CentralityMeasure,
+    /// Removed nodes first, then by size of the rank shift.
+    pub ranking_changes: Vec<RankChange>,
+}
+
+impl PerturbationReport {
+    /// Drop in apoptosis–metabolism path confidence; 0 if there was no path.
+    pub fn path_confidence_loss(&self) -> f64 {
+        path_confidence_loss(&self.module_path_before, &self.module_path_after)
+    }
+
+    /// True if the modules were connected before and are not after.
+    pub fn disconnects_modules(&self) -> bool {
+        self.module_path_before.is_some() && self.module_path_after.is_none()
+    }
+
+    /// `connectivity_loss + path_confidence_loss`, in `[0, 2]`.
+    pub fn impact(&self) -> f64 {
+        self.connectivity_loss + self.path_confidence_loss()
+    }
+}
+
+/// One row of a knockout screen.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KnockoutImpact {
+    pub node: String,
+    pub connectivity_loss: f64,
+    pub path_confidence_before: Option<f64>,
+    pub path_confidence_after: Option<f64>,
+    pub impact: f64,
+}
+
+fn path_confidence_loss(before: &Option<NetworkPath>, after: &Option<NetworkPath>) -> f64 {
+    let confidence = |path: &Option<NetworkPath>| path.as_ref().map_or(0.0, |p| p.confidence);
+    (confidence(before) - confidence(after)).max(0.0)
+}
+
+fn connectivity_loss(before: usize, after: usize) -> f64 {
+    if before == 0 {
+        0.0
+    } else {
+        1.0 - after as f64 / before as f64
+    }
+}
+
+/// Scores perturbations against the apoptosis and metabolism modules.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PerturbationAnalysis {
+    pub apoptosis: Vec<String>,
+    pub metabolism: Vec<String>,
+    pub measure: CentralityMeasure,
+}
+
+impl Default for PerturbationAnalysis {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PerturbationAnalysis {
+    /// `APOPTOSIS_GENES` against every metabolic gene set the analyzer
+    /// knows, with rankings by betweenness.
+    pub fn new() -> Self {
+        Self {
+            apoptosis: APOPTOSIS_GENES.iter().map(|g| g.to_string()).collect(),
+            metabolism: METABOLIC_GENE_SETS
+                .iter()
+                .flat_map(|(_, genes)| genes.iter().map(|g| g.to_string()))
+                .collect(),
+            measure: CentralityMeasure::Betweenness,
+        }
+    }
+
+    pub fn with_modules(mut self, apoptosis: &[&str], metabolism: &[&str]) -> Self {
+        self.apoptosis = apoptosis.iter().map(|g| g.to_string()).collect();
+        self.metabolism = metabolism.iter().map(|g| g.to_string()).collect();
+        self
+    }
+
+    pub fn with_measure(mut self, measure: CentralityMeasure) -> Self {
+        self.measure = measure;
+        self
+    }
+
+    /// Most confident path from the apoptosis to the metabolism module.
+    pub fn module_path(&self, network: &InteractionNetwork) -> Option<NetworkPath> {
+        let apoptosis: Vec<&str> = self.apoptosis.iter().map(String::as_str).collect();
+        let metabolism: Vec<&str> = self.metabolism.iter().map(String::as_str).collect();
+        network.best_path_between(&apoptosis, &metabolism)
+    }
+
+    pub fn perturb(&self, network: &InteractionNetwork, perturbation: &Perturbation) -> Result<PerturbationReport> {
+        let perturbed = perturbation.apply(network)?;
+        let (components_before, components_after) = (network.connected_components(), perturbed.connected_components());
+        let largest = |components: &[Vec<String>]| components.first().map_or(0, Vec::len);
+
+        Ok(PerturbationReport {
+            perturbation: perturbation.clone(),
+            components_before: components_before.len(),
+            components_after: components_after.len(),
+            largest_component_before: largest(&components_before),
+            largest_component_after: largest(&components_after),
+            connectivity_loss: connectivity_loss(network.pairwise_connectivity(), perturbed.pairwise_connectivity()),
+            module_path_before: self.module_path(network),
+            module_path_after: self.module_path(&perturbed),
+            measure: self.measure,
+            ranking_changes: self.ranking_changes(&network.centrality_table(), &perturbed.centrality_table()),
+        })
+    }
+
+    fn ranking_changes(&self, before: &CentralityTable, after: &CentralityTable) -> Vec<RankChange> {
+        let mut changes: Vec<RankChange> = before
+            .ranked(self.measure)
+            .iter()
+            .enumerate()
+            .map(|(i, row)| RankChange {
+                node: row.node.clone(),
+                rank_before: i + 1,
+                rank_after: after.rank_of(&row.node, self.measure),
+                score_before: row.score(self.measure),
+                score_after: after.row(&row.node).map(|r| r.score(self.measure)),
+            })
+            .collect();
+        changes.sort_by_key(|change| (change.shift().map(i64::abs).map(std::cmp::Reverse), change.rank_before));
+        changes
+    }
+
+    /// Knocks out every node in turn, most disruptive first.
+    pub fn knockout_screen(&self, network: &InteractionNetwork) -> Vec<KnockoutImpact> {
+        let connectivity = network.pairwise_connectivity();
+        let path_before = self.module_path(network);
+
+        let mut impacts: Vec<KnockoutImpact> = network
+            .nodes()
+            .iter()
+            .map(|node| {
+                let perturbed = network.without_nodes(&[node]);
+                let path_after = self.module_path(&perturbed);
+                let connectivity_loss = connectivity_loss(connectivity, perturbed.pairwise_connectivity());
+                KnockoutImpact {
+                    node: node.clone(),
+                    connectivity_loss,
+                    path_confidence_before: path_before.as_ref().map(|p| p.confidence),
+                    path_confidence_after: path_after.as_ref().map(|p| p.confidence),
+                    impact: connectivity_loss + path_confidence_loss(&path_before, &path_after),
+                }
+            })
+            .collect();
+        impacts.sort_by(|a, b| b.impact.total_cmp(&a.impact).then_with(|| a.node.cmp(&b.node)));
+        impacts
+    }
+}
+
+impl InteractionNetwork {
+    /// A copy without `nodes` and their edges; other nodes keep their
+    /// attributes, even if left isolated. Unknown names are ignored.
+    pub fn without_nodes(&self, nodes: &[&str]) -> Self {
+        let remaining: Vec<&str> = self
+            .nodes()
+            .iter()
+            .map(String::as_str)
+            .filter(|node| !nodes.contains(node))
+            .collect();
+        self.induced_subgraph(&remaining)
+    }
+
+    /// A copy without any edge joining a listed pair, in either orientation.
+    /// Every node is kept.
+    pub fn without_edges(&self, pairs: &[(&str, &str)]) -> Self {
+        let mut network = InteractionNetwork::new();
+        for node in self.nodes() {
+            network.add_node(node);
+        }
+        network.node_attributes = self.node_attributes.clone();
+        for edge in self.edges() {
+            let removed = pairs
+                .iter()
+                .any(|&(a, b)| (edge.source == a && edge.target == b) || (edge.source == b && edge.target == a));
+            if !removed {
+                network.add_interaction(edge.clone());
+            }
+        }
+        network
+    }
+
+    /// A copy in which every edge of `node` keeps `residual` of its
+    /// confidence, modelling partial inhibition rather than loss.
+    pub fn inhibited(&self, node: &str, residual: f64) -> Result<Self> {
+        if !self.contains_node(node) {
+            return Err(MCL1Error::UnknownProtein(node.to_string()));
+        }
+        let residual = MCL1Error::check_range("residual", residual, 0.0, 1.0)?;
+        let mut network = self.clone();
+        network.update_confidence(|edge| {
+            if edge.source == node || edge.target == node {
+                edge.confidence * residual
+            } else {
+                edge.confidence
+            }
+        });
+        Ok(network)
+    }
+
+    /// Sorted members of each connected component, largest first; edge
+    /// direction is ignored and zero-confidence edges, which paths never
+    /// traverse, do not connect.
+    pub fn connected_components(&self) -> Vec<Vec<String>> {
+        let links = self.links();
+        let mut seen = vec![false; links.len()];
+        let mut components = Vec::new();
+        for start in 0..links.len() {
+            if seen[start] {
+                continue;
+            }
+            seen[start] = true;
+            let mut component = Vec::new();
+            let mut queue = VecDeque::from([start]);
+            while let Some(v) = queue.pop_front() {
+                component.push(self.node_name(v).to_string());
+                for link in links[v].iter().filter(|link| link.confidence > 0.0) {
+                    if !seen[link.node] {
+                        seen[link.node] = true;
+                        queue.push_back(link.node);
+                    }
+                }
+            }
+            component.sort();
+            components.push(component);
+        }
+        components.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+        components
+    }
+
+    /// Number of unordered node pairs joined by some path.
+    pub fn pairwise_connectivity(&self) -> usize {
+        self.connected_components()
+            .iter()
+            .map(|c| c.len() * (c.len() - 1) / 2)
+            .sum()
+    }
+}
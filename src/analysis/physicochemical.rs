@@ -0,0 +1,417 @@
+//! Physicochemical properties computed from a protein sequence
+//!
+//! Masses follow ExPASy ProtParam residue masses, extinction coefficients
+//! use the Pace et al. (1995) values at 280 nm, hydropathy is Kyte-Doolittle
+//! and the instability index is Guruprasad et al. (1990).
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::protein::Protein;
+
+const WATER_AVERAGE: f64 = 18.01528;
+const WATER_MONOISOTOPIC: f64 = 18.01056;
+
+/// pH window searched for the isoelectric point.
+const PI_SEARCH_RANGE: (f64, f64) = (0.0, 14.0);
+const PI_TOLERANCE: f64 = 1e-4;
+
+/// `(residue, average mass, monoisotopic mass)` of residues within a chain.
+const RESIDUE_MASSES: &[(char, f64, f64)] = &[
+    ('A', 71.0788, 71.03711),
+    ('R', 156.1875, 156.10111),
+    ('N', 114.1038, 114.04293),
+    ('D', 115.0886, 115.02694),
+    ('C', 103.1388, 103.00919),
+    ('E', 129.1155, 129.04259),
+    ('Q', 128.1307, 128.05858),
+    ('G', 57.0519, 57.02146),
+    ('H', 137.1411, 137.05891),
+    ('I', 113.1594, 113.08406),
+    ('L', 113.1594, 113.08406),
+    ('K', 128.1741, 128.09496),
+    ('M', 131.1926, 131.04049),
+    ('F', 147.1766, 147.06841),
+    ('P', 97.1167, 97.05276),
+    ('S', 87.0782, 87.03203),
+    ('T', 101.1051, 101.04768),
+    ('W', 186.2132, 186.07931),
+    ('Y', 163.1760, 163.06333),
+    ('V', 99.1326, 99.06841),
+    ('U', 150.0388, 150.95364),
+    ('O', 237.3018, 237.14773),
+    // Ambiguity codes take the mean of the residues they stand for.
+    ('B', 114.5962, 114.53494),
+    ('Z', 128.6231, 128.55059),
+    ('J', 113.1594, 113.08406),
+];
+
+/// Mass used for `X` and any residue missing from the table.
+const UNKNOWN_RESIDUE_MASS: (f64, f64) = (111.1254, 111.0);
+
+const KYTE_DOOLITTLE: &[(char, f64)] = &[
+    ('A', 1.8),
+    ('R', -4.5),
+    ('N', -3.5),
+    ('D', -3.5),
+    ('C', 2.5),
+    ('Q', -3.5),
+    ('E', -3.5),
+    ('G', -0.4),
+    ('H', -3.2),
+    ('I', 4.5),
+    ('L', 3.8),
+    ('K', -3.9),
+    ('M', 1.9),
+    ('F', 2.8),
+    ('P', -1.6),
+    ('S', -0.8),
+    ('T', -0.7),
+    ('W', -0.9),
+    ('Y', -1.3),
+    ('V', 4.2),
+];
+
+const INSTABILITY_ORDER: &str = "ACDEFGHIKLMNPQRSTVWY";
+
+/// Dipeptide instability weight values (DIWV); row is the first residue,
+/// column the second, both in `INSTABILITY_ORDER`.
+#[rustfmt::skip]
+const DIWV: [[f64; 20]; 20] = [
+    //  A       C       D       E       F       G       H       I       K       L       M       N       P       Q       R       S       T       V       W       Y
+    [  1.0,  44.94,  -7.49,   1.0,    1.0,    1.0,   -7.49,   1.0,    1.0,    1.0,    1.0,    1.0,   20.26,   1.0,    1.0,    1.0,    1.0,    1.0,    1.0,    1.0 ], // A
+    [  1.0,   1.0,   20.26,   1.0,    1.0,    1.0,   33.60,   1.0,    1.0,   20.26,  33.60,   1.0,   20.26,  -6.54,   1.0,    1.0,   33.60,  -6.54,  24.68,   1.0 ], // C
+    [  1.0,   1.0,    1.0,    1.0,   -6.54,   1.0,    1.0,    1.0,   -7.49,   1.0,    1.0,    1.0,    1.0,    1.0,   -6.54,  20.26, -14.03,   1.0,    1.0,    1.0 ], // D
+    [  1.0,  44.94,  20.26,  33.60,   1.0,    1.0,   -6.54,  20.26,   1.0,    1.0,    1.0,    1.0,   20.26,  20.26,   1.0,   20.26,   1.0,    1.0,  -14.03,   1.0 ], // E
+    [  1.0,   1.0,   13.34,   1.0,    1.0,    1.0,    1.0,    1.0,  -14.03,   1.0,    1.0,    1.0,   20.26,   1.0,    1.0,    1.0,    1.0,    1.0,    1.0,   33.601], // F
+    [ -7.49,  1.0,    1.0,   -6.54,   1.0,   13.34,   1.0,   -7.49,  -7.49,   1.0,    1.0,   -7.49,   1.0,    1.0,    1.0,    1.0,   -7.49,   1.0,   13.34,  -7.49 ], // G
+    [  1.0,   1.0,    1.0,    1.0,   -9.37,  -9.37,   1.0,   44.94,  24.68,   1.0,    1.0,   24.68,  -1.88,   1.0,    1.0,    1.0,   -6.54,   1.0,   -1.88,  44.94 ], // H
+    [  1.0,   1.0,    1.0,   44.94,   1.0,    1.0,   13.34,   1.0,   -7.49,  20.26,   1.0,    1.0,   -1.88,   1.0,    1.0,    1.0,    1.0,   -7.49,   1.0,    1.0 ], // I
+    [  1.0,   1.0,    1.0,    1.0,    1.0,   -7.49,   1.0,   -7.49,   1.0,   -7.49,  33.60,   1.0,   -6.54,  24.64,  33.60,   1.0,    1.0,   -7.49,   1.0,    1.0 ], // K
+    [  1.0,   1.0,    1.0,    1.0,    1.0,    1.0,    1.0,    1.0,   -7.49,   1.0,    1.0,    1.0,   20.26,  33.60,  20.26,   1.0,    1.0,    1.0,   24.68,   1.0 ], // L
+    [ 13.34,  1.0,    1.0,    1.0,    1.0,    1.0,   58.28,   1.0,    1.0,    1.0,   -1.88,   1.0,   44.94,  -6.54,  -6.54,  44.94,  -1.88,   1.0,    1.0,   24.68 ], // M
+    [  1.0,  -1.88,   1.0,    1.0,  -14.03, -14.03,   1.0,   44.94,  24.68,   1.0,    1.0,    1.0,   -1.88,  -6.54,   1.0,    1.0,   -7.49,   1.0,   -9.37,   1.0 ], // N
+    [ 20.26, -6.54,  -6.54,  18.38,  20.26,   1.0,    1.0,    1.0,    1.0,    1.0,   -6.54,   1.0,   20.26,  20.26,  -6.54,  20.26,   1.0,   20.26,  -1.88,   1.0 ], // P
+    [  1.0,  -6.54,  20.26,  20.26,  -6.54,   1.0,    1.0,    1.0,    1.0,    1.0,    1.0,    1.0,   20.26,  20.26,   1.0,   44.94,   1.0,   -6.54,   1.0,   -6.54 ], // Q
+    [  1.0,   1.0,    1.0,    1.0,    1.0,   -7.49,  20.26,   1.0,    1.0,    1.0,    1.0,   13.34,  20.26,  20.26,  58.28,  44.94,   1.0,    1.0,   58.28,  -6.54 ], // R
+    [  1.0,  33.60,   1.0,   20.26,   1.0,    1.0,    1.0,    1.0,    1.0,    1.0,    1.0,    1.0,   44.94,  20.26,  20.26,  20.26,   1.0,    1.0,    1.0,    1.0 ], // S
+    [  1.0,   1.0,    1.0,   20.26,  13.34,  -7.49,   1.0,    1.0,    1.0,    1.0,    1.0,  -14.03,   1.0,   -6.54,   1.0,    1.0,    1.0,    1.0,  -14.03,   1.0 ], // T
+    [  1.0,   1.0,  -14.03,   1.0,    1.0,   -7.49,   1.0,    1.0,   -1.88,   1.0,    1.0,    1.0,   20.26,   1.0,    1.0,    1.0,   -7.49,   1.0,    1.0,   -6.54 ], // V
+    [-14.03,  1.0,    1.0,    1.0,    1.0,   -9.37,  24.68,   1.0,    1.0,   13.34,  24.68,  13.34,   1.0,    1.0,    1.0,    1.0,  -14.03,  -7.49,   1.0,    1.0 ], // W
+    [ 24.68,  1.0,   24.68,  -6.54,   1.0,   -7.49,  13.34,   1.0,    1.0,    1.0,   44.94,   1.0,   13.34,   1.0,  -15.91,   1.0,   -7.49,   1.0,   -9.37,  13.34 ], // Y
+];
+
+/// Ionisable group pKa values used for charge and pI.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PkaSet {
+    pub n_terminus: f64,
+    pub c_terminus: f64,
+    pub cys: f64,
+    pub asp: f64,
+    pub glu: f64,
+    pub his: f64,
+    pub lys: f64,
+    pub arg: f64,
+    pub tyr: f64,
+}
+
+impl PkaSet {
+    pub const EMBOSS: PkaSet = PkaSet {
+        n_terminus: 8.6,
+        c_terminus: 3.6,
+        cys: 8.5,
+        asp: 3.9,
+        glu: 4.1,
+        his: 6.5,
+        lys: 10.8,
+        arg: 12.5,
+        tyr: 10.1,
+    };
+
+    pub const LEHNINGER: PkaSet = PkaSet {
+        n_terminus: 9.69,
+        c_terminus: 2.34,
+        cys: 8.33,
+        asp: 3.86,
+        glu: 4.25,
+        his: 6.0,
+        lys: 10.5,
+        arg: 12.4,
+        tyr: 10.0,
+    };
+
+    pub const SOLOMON: PkaSet = PkaSet {
+        n_terminus: 9.6,
+        c_terminus: 2.4,
+        cys: 8.3,
+        asp: 3.9,
+        glu: 4.3,
+        his: 6.0,
+        lys: 10.5,
+        arg: 12.5,
+        tyr: 10.1,
+    };
+
+    pub const DTASELECT: PkaSet = PkaSet {
+        n_terminus: 8.0,
+        c_terminus: 3.1,
+        cys: 8.5,
+        asp: 4.4,
+        glu: 4.4,
+        his: 6.5,
+        lys: 10.0,
+        arg: 12.0,
+        tyr: 10.0,
+    };
+
+    pub const SILLERO: PkaSet = PkaSet {
+        n_terminus: 8.2,
+        c_terminus: 3.2,
+        cys: 9.0,
+        asp: 4.0,
+        glu: 4.5,
+        his: 6.4,
+        lys: 10.4,
+        arg: 12.0,
+        tyr: 10.0,
+    };
+}
+
+impl Default for PkaSet {
+    fn default() -> Self {
+        PkaSet::EMBOSS
+    }
+}
+
+/// Molar extinction coefficients at 280 nm in M^-1 cm^-1.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ExtinctionCoefficient {
+    /// All cysteines reduced.
+    pub reduced: f64,
+    /// All cysteine pairs forming cystines.
+    pub cystines: f64,
+}
+
+/// Every property computed for one sequence.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SequenceProperties {
+    pub length: usize,
+    pub average_mass: f64,
+    pub monoisotopic_mass: f64,
+    pub isoelectric_point: f64,
+    pub extinction_coefficient: ExtinctionCoefficient,
+    pub gravy: f64,
+    pub instability_index: f64,
+}
+
+impl SequenceProperties {
+    pub fn compute(sequence: &str, pka: &PkaSet) -> Self {
+        Self {
+            length: residues(sequence).count(),
+            average_mass: average_mass(sequence),
+            monoisotopic_mass: monoisotopic_mass(sequence),
+            isoelectric_point: isoelectric_point(sequence, pka),
+            extinction_coefficient: extinction_coefficient(sequence),
+            gravy: gravy(sequence),
+            instability_index: instability_index(sequence),
+        }
+    }
+
+    /// Proteins with an instability index above 40 are predicted unstable.
+    pub fn is_stable(&self) -> bool {
+        self.instability_index <= 40.0
+    }
+}
+
+/// Differences between a protein's declared and computed values.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConsistencyReport {
+    pub protein_id: String,
+    pub declared_mass: f64,
+    pub computed_mass: f64,
+    /// `|declared - computed| / computed`, or `None` if no mass was declared.
+    pub mass_drift: Option<f64>,
+    pub declared_pi: f64,
+    pub computed_pi: f64,
+    /// `|declared - computed|` in pH units, or `None` if no pI was declared.
+    pub pi_drift: Option<f64>,
+    pub consistent: bool,
+}
+
+/// Tolerances for `check_consistency`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConsistencyTolerance {
+    pub relative_mass: f64,
+    pub pi: f64,
+}
+
+impl Default for ConsistencyTolerance {
+    fn default() -> Self {
+        Self {
+            relative_mass: 0.01,
+            pi: 0.5,
+        }
+    }
+}
+
+fn residues(sequence: &str) -> impl Iterator<Item = char> + '_ {
+    sequence
+        .chars()
+        .filter(|c| c.is_ascii_alphabetic())
+        .map(|c| c.to_ascii_uppercase())
+}
+
+fn residue_mass(residue: char) -> (f64, f64) {
+    RESIDUE_MASSES
+        .iter()
+        .find(|(r, _, _)| *r == residue)
+        .map(|(_, average, mono)| (*average, *mono))
+        .unwrap_or(UNKNOWN_RESIDUE_MASS)
+}
+
+pub fn average_mass(sequence: &str) -> f64 {
+    let residues: f64 = residues(sequence).map(|r| residue_mass(r).0).sum();
+    if residues == 0.0 {
+        0.0
+    } else {
+        residues + WATER_AVERAGE
+    }
+}
+
+pub fn monoisotopic_mass(sequence: &str) -> f64 {
+    let residues: f64 = residues(sequence).map(|r| residue_mass(r).1).sum();
+    if residues == 0.0 {
+        0.0
+    } else {
+        residues + WATER_MONOISOTOPIC
+    }
+}
+
+/// Net charge at `ph` by the Henderson-Hasselbalch equation.
+pub fn net_charge(sequence: &str, ph: f64, pka: &PkaSet) -> f64 {
+    let positive = |pk: f64| 1.0 / (1.0 + 10f64.powf(ph - pk));
+    let negative = |pk: f64| -1.0 / (1.0 + 10f64.powf(pk - ph));
+
+    let mut charge = positive(pka.n_terminus) + negative(pka.c_terminus);
+    for residue in residues(sequence) {
+        charge += match residue {
+            'K' => positive(pka.lys),
+            'R' => positive(pka.arg),
+            'H' => positive(pka.his),
+            'D' => negative(pka.asp),
+            'E' => negative(pka.glu),
+            'C' => negative(pka.cys),
+            'Y' => negative(pka.tyr),
+            _ => 0.0,
+        };
+    }
+    charge
+}
+
+/// Isoelectric point found by bisection on the net charge.
+pub fn isoelectric_point(sequence: &str, pka: &PkaSet) -> f64 {
+    let (mut low, mut high) = PI_SEARCH_RANGE;
+    while high - low > PI_TOLERANCE {
+        let mid = (low + high) / 2.0;
+        if net_charge(sequence, mid, pka) > 0.0 {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    (low + high) / 2.0
+}
+
+pub fn extinction_coefficient(sequence: &str) -> ExtinctionCoefficient {
+    let count = |target: char| residues(sequence).filter(|r| *r == target).count() as f64;
+    let reduced = count('W') * 5500.0 + count('Y') * 1490.0;
+    ExtinctionCoefficient {
+        reduced,
+        cystines: reduced + (count('C') / 2.0).floor() * 125.0,
+    }
+}
+
+/// Grand average of hydropathy; residues without a Kyte-Doolittle value are
+/// left out of the average.
+pub fn gravy(sequence: &str) -> f64 {
+    let values: Vec<f64> = residues(sequence)
+        .filter_map(|r| KYTE_DOOLITTLE.iter().find(|(k, _)| *k == r).map(|(_, v)| *v))
+        .collect();
+    if values.is_empty() {
+        0.0
+    } else {
+        values.iter().sum::<f64>() / values.len() as f64
+    }
+}
+
+/// Instability index; dipeptides containing non-standard residues are skipped.
+pub fn instability_index(sequence: &str) -> f64 {
+    let indices: Vec<Option<usize>> = residues(sequence).map(|r| INSTABILITY_ORDER.find(r)).collect();
+    if indices.is_empty() {
+        return 0.0;
+    }
+
+    let score: f64 = indices
+        .windows(2)
+        .filter_map(|pair| match (pair[0], pair[1]) {
+            (Some(first), Some(second)) => Some(DIWV[first][second]),
+            _ => None,
+        })
+        .sum();
+    10.0 / indices.len() as f64 * score
+}
+
+/// Compares a protein's declared mass and pI against its sequence. Declared
+/// values of zero are treated as missing and never flagged.
+pub fn check_consistency(protein: &Protein, pka: &PkaSet, tolerance: &ConsistencyTolerance) -> ConsistencyReport {
+    let computed_mass = average_mass(&protein.sequence);
+    let computed_pi = isoelectric_point(&protein.sequence, pka);
+
+    let mass_drift = (protein.molecular_weight > 0.0 && computed_mass > 0.0)
+        .then(|| (protein.molecular_weight - computed_mass).abs() / computed_mass);
+    let pi_drift = (protein.isoelectric_point > 0.0).then(|| (protein.isoelectric_point - computed_pi).abs());
+
+    let consistent = mass_drift.is_none_or(|d| d <= tolerance.relative_mass)
+        && pi_drift.is_none_or(|d| d <= tolerance.pi);
+
+    ConsistencyReport {
+        protein_id: protein.id.clone(),
+        declared_mass: protein.molecular_weight,
+        computed_mass,
+        mass_drift,
+        declared_pi: protein.isoelectric_point,
+        computed_pi,
+        pi_drift,
+        consistent,
+    }
+}
+
+/// Reports only the proteins whose declared values drift past `tolerance`.
+pub fn find_inconsistent<'a, I>(proteins: I, pka: &PkaSet, tolerance: &ConsistencyTolerance) -> Vec<ConsistencyReport>
+where
+    I: IntoIterator<Item = &'a Protein>,
+{
+    proteins
+        .into_iter()
+        .map(|protein| check_consistency(protein, pka, tolerance))
+        .filter(|report| !report.consistent)
+        .collect()
+}
+
+impl Protein {
+    /// Builds a protein whose mass and pI are computed from `sequence`
+    /// using the default (EMBOSS) pKa set.
+    pub fn from_sequence(id: String, name: String, sequence: String) -> Self {
+        let molecular_weight = average_mass(&sequence);
+        let isoelectric_point = isoelectric_point(&sequence, &PkaSet::default());
+        Protein::new(id, name, sequence, molecular_weight, isoelectric_point)
+    }
+
+    pub fn properties(&self, pka: &PkaSet) -> SequenceProperties {
+        SequenceProperties::compute(&self.sequence, pka)
+    }
+
+    /// Compares the declared mass and pI with the values computed from the sequence.
+    pub fn check_consistency(&self, tolerance: &ConsistencyTolerance) -> ConsistencyReport {
+        check_consistency(self, &PkaSet::default(), tolerance)
+    }
+}
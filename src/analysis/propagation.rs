@@ -0,0 +1,328 @@
+//! Network propagation from a seed set
+//!
+//! Scores every node by its proximity to the seeds under random walk with
+//! restart, heat diffusion or personalized PageRank, and estimates
+//! empirical p-values by re-running propagation from degree-matched random
+//! seed sets. Edge weights are confidences.
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::interaction_network::{InteractionNetwork, Link};
+use crate::error::{MCL1Error, Result};
+
+pub const DEFAULT_RESTART: f64 = 0.5;
+pub const DEFAULT_DIFFUSION_TIME: f64 = 1.0;
+pub const DEFAULT_DAMPING: f64 = 0.85;
+
+/// Convergence threshold (L1 change per iteration) for iterative methods.
+const TOLERANCE: f64 = 1e-10;
+const MAX_ITERATIONS: usize = 1000;
+/// Seeds are swapped for random nodes from a bin of at least this many
+/// nodes of similar degree.
+const MIN_BIN_SIZE: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Propagation {
+    /// Walk over undirected links that returns to the seeds with
+    /// probability `restart` at each step (Köhler et al., 2008); a walker
+    /// at a node without links also returns to the seeds.
+    RandomWalkWithRestart { restart: f64 },
+    /// Seed heat after `time` of diffusion along the weighted graph
+    /// Laplacian, `exp(-time L) h0`.
+    HeatDiffusion { time: f64 },
+    /// PageRank that teleports to the seeds and follows edge direction;
+    /// rank stuck at nodes without outgoing edges also returns to the seeds.
+    PersonalizedPageRank { damping: f64 },
+}
+
+impl Propagation {
+    fn validate(&self) -> Result<()> {
+        match *self {
+            Propagation::RandomWalkWithRestart { restart } => MCL1Error::check_range("restart", restart, 0.0, 1.0),
+            Propagation::HeatDiffusion { time } => MCL1Error::check_range("time", time, 0.0, f64::INFINITY),
+            Propagation::PersonalizedPageRank { damping } => MCL1Error::check_range("damping", damping, 0.0, 1.0),
+        }
+        .map(|_| ())
+    }
+}
+
+/// A node's proximity to the seed set.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeProximity {
+    pub node: String,
+    pub score: f64,
+    /// Fraction of random seed sets giving at least this score, if tested.
+    pub p_value: Option<f64>,
+}
+
+/// Proximity of every node, highest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProximityScores {
+    pub method: Propagation,
+    pub seeds: Vec<String>,
+    pub rows: Vec<NodeProximity>,
+}
+
+impl ProximityScores {
+    pub fn get(&self, node: &str) -> Option<&NodeProximity> {
+        self.rows.iter().find(|row| row.node == node)
+    }
+
+    /// 1-based rank of `node`.
+    pub fn rank_of(&self, node: &str) -> Option<usize> {
+        self.rows.iter().position(|row| row.node == node).map(|i| i + 1)
+    }
+
+    /// Rows for nodes outside the seed set, so seeds don't crowd the top.
+    pub fn non_seeds(&self) -> Vec<&NodeProximity> {
+        self.rows.iter().filter(|row| !self.seeds.contains(&row.node)).collect()
+    }
+}
+
+fn strengths(links: &[Vec<Link>]) -> Vec<f64> {
+    links.iter().map(|l| l.iter().map(|link| link.weight).sum()).collect()
+}
+
+fn random_walk(links: &[Vec<Link>], start: &[f64], restart: f64) -> Vec<f64> {
+    let strength = strengths(links);
+    let mut scores = start.to_vec();
+    for _ in 0..MAX_ITERATIONS {
+        let dangling: f64 = (0..scores.len())
+            .filter(|&v| strength[v] == 0.0)
+            .map(|v| scores[v])
+            .sum();
+        let mut next: Vec<f64> = start.iter().map(|s| (restart + (1.0 - restart) * dangling) * s).collect();
+        for (v, links) in links.iter().enumerate().filter(|(v, _)| strength[*v] > 0.0) {
+            for link in links {
+                next[link.node] += (1.0 - restart) * scores[v] * link.weight / strength[v];
+            }
+        }
+        let change: f64 = next.iter().zip(&scores).map(|(a, b)| (a - b).abs()).sum();
+        scores = next;
+        if change < TOLERANCE {
+            break;
+        }
+    }
+    scores
+}
+
+/// `exp(-time L) h0` by Taylor series over steps short enough that each
+/// series converges quickly.
+fn heat_diffusion(links: &[Vec<Link>], start: &[f64], time: f64) -> Vec<f64> {
+    let strength = strengths(links);
+    let bound = 2.0 * strength.iter().copied().fold(0.0, f64::max);
+    let steps = (time * bound).ceil().max(1.0) as usize;
+    let dt = time / steps as f64;
+    let laplacian = |v: &[f64]| -> Vec<f64> {
+        (0..v.len())
+            .map(|i| strength[i] * v[i] - links[i].iter().map(|link| link.weight * v[link.node]).sum::<f64>())
+            .collect()
+    };
+
+    let mut heat = start.to_vec();
+    for _ in 0..steps {
+        let mut term = heat.clone();
+        for k in 1..=MAX_ITERATIONS {
+            term = laplacian(&term).into_iter().map(|x| -dt * x / k as f64).collect();
+            heat.iter_mut().zip(&term).for_each(|(h, t)| *h += t);
+            if term.iter().map(|t| t.abs()).sum::<f64>() < TOLERANCE {
+                break;
+            }
+        }
+    }
+    heat
+}
+
+impl InteractionNetwork {
+    /// Outgoing `(node, weight)` steps per node, following edge direction.
+    fn out_steps(&self) -> Vec<Vec<(usize, f64)>> {
+        let mut steps = vec![Vec::new(); self.node_count()];
+        for (v, steps) in steps.iter_mut().enumerate() {
+            let name = self.node_name(v);
+            for (neighbor, edge) in self.incident(v) {
+                if neighbor != v && edge.confidence > 0.0 && edge.leaves(name) {
+                    steps.push((neighbor, edge.confidence));
+                }
+            }
+        }
+        steps
+    }
+
+    fn personalized_pagerank(&self, start: &[f64], damping: f64) -> Vec<f64> {
+        let steps = self.out_steps();
+        let strength: Vec<f64> = steps.iter().map(|s| s.iter().map(|(_, w)| w).sum()).collect();
+        let mut scores = start.to_vec();
+        for _ in 0..MAX_ITERATIONS {
+            let dangling: f64 = (0..scores.len())
+                .filter(|&v| strength[v] == 0.0)
+                .map(|v| scores[v])
+                .sum();
+            let mut next: Vec<f64> = start.iter().map(|s| (1.0 - damping + damping * dangling) * s).collect();
+            for (v, steps) in steps.iter().enumerate().filter(|(v, _)| strength[*v] > 0.0) {
+                for &(u, weight) in steps {
+                    next[u] += damping * scores[v] * weight / strength[v];
+                }
+            }
+            let change: f64 = next.iter().zip(&scores).map(|(a, b)| (a - b).abs()).sum();
+            scores = next;
+            if change < TOLERANCE {
+                break;
+            }
+        }
+        scores
+    }
+
+    fn seed_indices(&self, seeds: &[&str]) -> Result<Vec<usize>> {
+        let mut indices = Vec::new();
+        for seed in seeds {
+            let index = self
+                .node_index(seed)
+                .ok_or_else(|| MCL1Error::UnknownProtein(seed.to_string()))?;
+            if !indices.contains(&index) {
+                indices.push(index);
+            }
+        }
+        if indices.is_empty() {
+            return Err(MCL1Error::MissingParameter("propagation seeds".to_string()));
+        }
+        Ok(indices)
+    }
+
+    fn propagate_from(&self, links: &[Vec<Link>], seeds: &[usize], method: Propagation) -> Vec<f64> {
+        let mut start = vec![0.0; self.node_count()];
+        for &seed in seeds {
+            start[seed] = 1.0 / seeds.len() as f64;
+        }
+        match method {
+            Propagation::RandomWalkWithRestart { restart } => random_walk(links, &start, restart),
+            Propagation::HeatDiffusion { time } => heat_diffusion(links, &start, time),
+            Propagation::PersonalizedPageRank { damping } => self.personalized_pagerank(&start, damping),
+        }
+    }
+
+    fn proximity_scores(
+        &self,
+        seeds: &[usize],
+        method: Propagation,
+        scores: Vec<f64>,
+        p_values: Option<Vec<f64>>,
+    ) -> ProximityScores {
+        let mut rows: Vec<NodeProximity> = scores
+            .into_iter()
+            .enumerate()
+            .map(|(i, score)| NodeProximity {
+                node: self.node_name(i).to_string(),
+                score,
+                p_value: p_values.as_ref().map(|p| p[i]),
+            })
+            .collect();
+        rows.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.node.cmp(&b.node)));
+        ProximityScores {
+            method,
+            seeds: seeds.iter().map(|&s| self.node_name(s).to_string()).collect(),
+            rows,
+        }
+    }
+
+    /// Proximity of every node to `seeds`, which share the initial mass equally.
+    pub fn propagate(&self, seeds: &[&str], method: Propagation) -> Result<ProximityScores> {
+        method.validate()?;
+        let seeds = self.seed_indices(seeds)?;
+        let scores = self.propagate_from(&self.links(), &seeds, method);
+        Ok(self.proximity_scores(&seeds, method, scores, None))
+    }
+
+    /// As `propagate`, with each node's p-value estimated from
+    /// `permutations` runs whose seeds are replaced by random nodes of
+    /// similar degree. `rng_seed` makes the test reproducible.
+    pub fn propagation_significance(
+        &self,
+        seeds: &[&str],
+        method: Propagation,
+        permutations: usize,
+        rng_seed: u64,
+    ) -> Result<ProximityScores> {
+        method.validate()?;
+        let seeds = self.seed_indices(seeds)?;
+        let links = self.links();
+        let observed = self.propagate_from(&links, &seeds, method);
+        let (bins, bin_of) = degree_bins(&links);
+        let mut rng = StdRng::seed_from_u64(rng_seed);
+
+        let mut exceed = vec![0usize; observed.len()];
+        for _ in 0..permutations {
+            let random_seeds = degree_matched(&seeds, &bins, &bin_of, &mut rng);
+            let scores = self.propagate_from(&links, &random_seeds, method);
+            for (count, (random, observed)) in exceed.iter_mut().zip(scores.iter().zip(&observed)) {
+                if *random >= observed - TOLERANCE {
+                    *count += 1;
+                }
+            }
+        }
+
+        let p_values = exceed
+            .iter()
+            .map(|&count| (count + 1) as f64 / (permutations + 1) as f64)
+            .collect();
+        Ok(self.proximity_scores(&seeds, method, observed, Some(p_values)))
+    }
+}
+
+/// Nodes sorted by degree and cut into bins of at least `MIN_BIN_SIZE`,
+/// never splitting a degree across bins, with the bin of every node.
+fn degree_bins(links: &[Vec<Link>]) -> (Vec<Vec<usize>>, Vec<usize>) {
+    let mut order: Vec<usize> = (0..links.len()).collect();
+    order.sort_by_key(|&v| links[v].len());
+
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    for v in order {
+        match groups.last_mut() {
+            Some(group) if group.len() < MIN_BIN_SIZE || links[group[group.len() - 1]].len() == links[v].len() => {
+                group.push(v)
+            }
+            _ => groups.push(vec![v]),
+        }
+    }
+    // A short trailing bin joins the previous one.
+    if groups.len() > 1 && groups.last().is_some_and(|g| g.len() < MIN_BIN_SIZE) {
+        let last = groups.pop().unwrap_or_default();
+        if let Some(previous) = groups.last_mut() {
+            previous.extend(last);
+        }
+    }
+
+    let mut bin_of = vec![0; links.len()];
+    for (bin, group) in groups.iter().enumerate() {
+        for &v in group {
+            bin_of[v] = bin;
+        }
+    }
+    (groups, bin_of)
+}
+
+/// A random seed set with one node drawn from each seed's degree bin,
+/// without repeats. A seed whose bin is used up draws from the nearest bin
+/// with nodes left, so the set is always as large as `seeds`.
+fn degree_matched(seeds: &[usize], bins: &[Vec<usize>], bin_of: &[usize], rng: &mut StdRng) -> Vec<usize> {
+    let mut chosen: Vec<usize> = Vec::with_capacity(seeds.len());
+    for &seed in seeds {
+        let home = bin_of[seed];
+        for offset in 0..bins.len() {
+            let nearest = [home.checked_sub(offset), Some(home + offset).filter(|&b| offset > 0 && b < bins.len())];
+            let candidates: Vec<usize> = nearest
+                .iter()
+                .flatten()
+                .flat_map(|&bin| bins[bin].iter().copied())
+                .filter(|v| !chosen.contains(v))
+                .collect();
+            if let Some(&v) = candidates.choose(rng) {
+                chosen.push(v);
+                break;
+            }
+        }
+    }
+    chosen
+}
@@ -0,0 +1,254 @@
+//! Rule engine linking modification state to half-life and binding
+//!
+//! Each rule fires when all of its site conditions hold and multiplies
+//! either the protein's half-life or the strength of some of its
+//! interactions. Effects of several rules compound multiplicatively.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::analysis::interaction_network::InteractionNetwork;
+use crate::error::{MCL1Error, Result};
+use crate::models::protein::Protein;
+use crate::models::ptm::{ModificationType, PtmSite};
+
+/// Basal MCL1 half-life in unstimulated cells, in minutes.
+pub const MCL1_HALF_LIFE_MINUTES: f64 = 40.0;
+
+/// Known MCL1 sites as `(residue, position, modification, writer)`:
+/// CDK1 at S64, JNK1 at S121, GSK3B at S159, ERK at T163, and the
+/// MULE (HUWE1) ubiquitination lysines.
+pub const MCL1_PTM_SITES: &[(char, usize, ModificationType, &str)] = &[
+    ('K', 5, ModificationType::Ubiquitination, "HUWE1"),
+    ('K', 40, ModificationType::Ubiquitination, "HUWE1"),
+    ('S', 64, ModificationType::Phosphorylation, "CDK1"),
+    ('S', 121, ModificationType::Phosphorylation, "MAPK8"),
+    ('K', 136, ModificationType::Ubiquitination, "HUWE1"),
+    ('S', 159, ModificationType::Phosphorylation, "GSK3B"),
+    ('T', 163, ModificationType::Phosphorylation, "MAPK1"),
+    ('K', 194, ModificationType::Ubiquitination, "HUWE1"),
+    ('K', 197, ModificationType::Ubiquitination, "HUWE1"),
+];
+
+/// Records the `MCL1_PTM_SITES` on an MCL1 protein, all unmodified.
+pub fn annotate_mcl1_sites(protein: &mut Protein) -> Result<()> {
+    for (residue, position, modification, enzyme) in MCL1_PTM_SITES {
+        protein.add_ptm_site(PtmSite::new(*residue, *position, *modification).with_enzyme(enzyme))?;
+    }
+    Ok(())
+}
+
+/// Matches sites by modification and optionally by position and enzyme.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SiteCondition {
+    pub modification: ModificationType,
+    /// `None` matches any site with this modification.
+    pub position: Option<usize>,
+    pub enzyme: Option<String>,
+    /// Required state of the matched site.
+    pub modified: bool,
+}
+
+impl SiteCondition {
+    pub fn modified(modification: ModificationType, position: Option<usize>) -> Self {
+        Self {
+            modification,
+            position,
+            enzyme: None,
+            modified: true,
+        }
+    }
+
+    pub fn unmodified(modification: ModificationType, position: Option<usize>) -> Self {
+        Self {
+            modified: false,
+            ..Self::modified(modification, position)
+        }
+    }
+
+    pub fn with_enzyme(mut self, enzyme: &str) -> Self {
+        self.enzyme = Some(enzyme.to_string());
+        self
+    }
+
+    /// True if some site matches; for an unmodified condition every
+    /// matching site must be unmodified, and absent sites count as such.
+    pub fn holds(&self, protein: &Protein) -> bool {
+        let mut sites = protein.ptm_sites.iter().filter(|site| {
+            site.modification == self.modification
+                && self.position.is_none_or(|p| p == site.position)
+                && self
+                    .enzyme
+                    .as_deref()
+                    .is_none_or(|e| site.enzyme.as_deref().is_some_and(|s| s.eq_ignore_ascii_case(e)))
+        });
+        if self.modified {
+            sites.any(|site| site.modified)
+        } else {
+            sites.all(|site| !site.modified)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PtmEffect {
+    /// Multiplies the protein's half-life.
+    HalfLife(f64),
+    /// Multiplies the confidence of interactions with `partners`, or with
+    /// every partner if the list is empty.
+    InteractionStrength { partners: Vec<String>, factor: f64 },
+}
+
+impl PtmEffect {
+    fn factor(&self) -> f64 {
+        match self {
+            PtmEffect::HalfLife(factor) => *factor,
+            PtmEffect::InteractionStrength { factor, .. } => *factor,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PtmRule {
+    pub name: String,
+    pub conditions: Vec<SiteCondition>,
+    pub effect: PtmEffect,
+}
+
+impl PtmRule {
+    pub fn new(name: &str, effect: PtmEffect) -> Self {
+        Self {
+            name: name.to_string(),
+            conditions: Vec::new(),
+            effect,
+        }
+    }
+
+    pub fn when(mut self, condition: SiteCondition) -> Self {
+        self.conditions.push(condition);
+        self
+    }
+
+    pub fn applies(&self, protein: &Protein) -> bool {
+        self.conditions.iter().all(|condition| condition.holds(protein))
+    }
+}
+
+/// Combined effect of the rules that fired for one protein.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PtmOutcome {
+    pub protein_id: String,
+    pub half_life: f64,
+    /// Factor applied to every interaction.
+    pub global_interaction_factor: f64,
+    /// Additional factors for specific partners.
+    pub partner_factors: HashMap<String, f64>,
+    pub fired_rules: Vec<String>,
+}
+
+impl PtmOutcome {
+    pub fn interaction_factor(&self, partner: &str) -> f64 {
+        self.global_interaction_factor * self.partner_factors.get(partner).copied().unwrap_or(1.0)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PtmRuleEngine {
+    pub baseline_half_life: f64,
+    pub rules: Vec<PtmRule>,
+}
+
+impl Default for PtmRuleEngine {
+    fn default() -> Self {
+        Self::mcl1()
+    }
+}
+
+impl PtmRuleEngine {
+    pub fn new(baseline_half_life: f64) -> Self {
+        Self {
+            baseline_half_life,
+            rules: Vec::new(),
+        }
+    }
+
+    /// MCL1 turnover and binding rules: ERK phosphorylation of T163
+    /// stabilizes MCL1 unless GSK3B has phosphorylated S159, which primes
+    /// FBW7-mediated degradation; ubiquitination by MULE or FBW7 sends it
+    /// to the proteasome; S64 phosphorylation strengthens binding of
+    /// pro-apoptotic partners.
+    pub fn mcl1() -> Self {
+        let phospho = ModificationType::Phosphorylation;
+        let rules = vec![
+            PtmRule::new("T163 phosphorylation stabilizes MCL1", PtmEffect::HalfLife(2.0))
+                .when(SiteCondition::modified(phospho, Some(163)))
+                .when(SiteCondition::unmodified(phospho, Some(159))),
+            PtmRule::new("S159 phosphorylation primes FBW7 degradation", PtmEffect::HalfLife(0.5))
+                .when(SiteCondition::modified(phospho, Some(159))),
+            PtmRule::new("Ubiquitinated MCL1 is degraded", PtmEffect::HalfLife(0.25))
+                .when(SiteCondition::modified(ModificationType::Ubiquitination, None)),
+            PtmRule::new(
+                "S64 phosphorylation enhances BH3 partner binding",
+                PtmEffect::InteractionStrength {
+                    partners: vec!["BAK1".to_string(), "BCL2L11".to_string(), "PMAIP1".to_string()],
+                    factor: 1.2,
+                },
+            )
+            .when(SiteCondition::modified(phospho, Some(64))),
+        ];
+        Self {
+            baseline_half_life: MCL1_HALF_LIFE_MINUTES,
+            rules,
+        }
+    }
+
+    pub fn with_rule(mut self, rule: PtmRule) -> Result<Self> {
+        MCL1Error::check_range(&rule.name, rule.effect.factor(), 0.0, f64::INFINITY)?;
+        self.rules.push(rule);
+        Ok(self)
+    }
+
+    pub fn evaluate(&self, protein: &Protein) -> PtmOutcome {
+        let mut outcome = PtmOutcome {
+            protein_id: protein.id.clone(),
+            half_life: self.baseline_half_life,
+            global_interaction_factor: 1.0,
+            partner_factors: HashMap::new(),
+            fired_rules: Vec::new(),
+        };
+
+        for rule in self.rules.iter().filter(|rule| rule.applies(protein)) {
+            match &rule.effect {
+                PtmEffect::HalfLife(factor) => outcome.half_life *= factor,
+                PtmEffect::InteractionStrength { partners, factor } if partners.is_empty() => {
+                    outcome.global_interaction_factor *= factor;
+                }
+                PtmEffect::InteractionStrength { partners, factor } => {
+                    for partner in partners {
+                        *outcome.partner_factors.entry(partner.clone()).or_insert(1.0) *= factor;
+                    }
+                }
+            }
+            outcome.fired_rules.push(rule.name.clone());
+        }
+        outcome
+    }
+
+    /// Evaluates `protein` and rescales the confidence of its edges in
+    /// `network`, matched by protein ID or name, capping at 1.
+    pub fn apply_to_network(&self, protein: &Protein, network: &mut InteractionNetwork) -> PtmOutcome {
+        let outcome = self.evaluate(protein);
+        let is_protein = |node: &str| node == protein.id || node == protein.name;
+        network.update_confidence(|edge| {
+            let partner = if is_protein(&edge.source) {
+                &edge.target
+            } else if is_protein(&edge.target) {
+                &edge.source
+            } else {
+                return edge.confidence;
+            };
+            (edge.confidence * outcome.interaction_factor(partner)).min(1.0)
+        });
+        outcome
+    }
+}
@@ -0,0 +1,224 @@
+//! Degree-preserving null models and permutation testing
+//!
+//! Random networks are produced by repeated double edge swaps:
+//! `a-b, c-d` become `a-d, c-b`. This keeps every node's degree, and the
+//! in- and out-degree of directed edges, while scrambling who connects to
+//! whom. Directed edges are only swapped with directed ones, and swaps that
+//! would create a self-loop or repeat an existing node pair are rejected.
+//! Edges keep their type, confidence, direction and sign.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::analysis::interaction_network::{InteractionNetwork, ProteinInteraction};
+use crate::error::{MCL1Error, Result};
+use crate::models::interaction::Direction;
+
+pub const DEFAULT_SWAPS_PER_EDGE: usize = 10;
+pub const DEFAULT_PERMUTATIONS: usize = 1000;
+
+/// Attempts per requested swap before giving up on dense networks.
+const MAX_ATTEMPTS_PER_SWAP: usize = 10;
+
+fn pair(a: usize, b: usize) -> (usize, usize) {
+    (a.min(b), a.max(b))
+}
+
+impl InteractionNetwork {
+    /// A copy with `swaps_per_edge` successful swaps per edge, where the
+    /// network allows that many. Nodes and node attributes are kept.
+    pub fn randomized<R: Rng>(&self, swaps_per_edge: usize, rng: &mut R) -> Self {
+        let mut endpoints: Vec<(usize, usize)> = self
+            .edges()
+            .iter()
+            .filter_map(|edge| Some((self.node_index(&edge.source)?, self.node_index(&edge.target)?)))
+            .collect();
+        let undirected: Vec<bool> = self
+            .edges()
+            .iter()
+            .map(|e| e.direction() == Direction::Undirected)
+            .collect();
+        // Edge count per node pair, so parallel edges free a pair only once all have moved.
+        let mut pairs: HashMap<(usize, usize), usize> = HashMap::new();
+        for &(a, b) in &endpoints {
+            *pairs.entry(pair(a, b)).or_insert(0) += 1;
+        }
+
+        let m = endpoints.len();
+        let wanted = swaps_per_edge * m;
+        let mut swapped = 0;
+        for _ in 0..wanted * MAX_ATTEMPTS_PER_SWAP {
+            if swapped >= wanted || m < 2 {
+                break;
+            }
+            let (i, j) = (rng.gen_range(0..m), rng.gen_range(0..m));
+            let (a, mut b) = endpoints[i];
+            let (mut c, mut d) = endpoints[j];
+            if i == j || undirected[i] != undirected[j] {
+                continue;
+            }
+            // Undirected edges may be used either way round.
+            if undirected[j] && rng.gen_bool(0.5) {
+                std::mem::swap(&mut c, &mut d);
+            }
+            if a == d || c == b || pairs.contains_key(&pair(a, d)) || pairs.contains_key(&pair(c, b)) {
+                continue;
+            }
+            for old in [pair(a, b), pair(c, d)] {
+                if let Some(count) = pairs.get_mut(&old) {
+                    *count -= 1;
+                    if *count == 0 {
+                        pairs.remove(&old);
+                    }
+                }
+            }
+            *pairs.entry(pair(a, d)).or_insert(0) += 1;
+            *pairs.entry(pair(c, b)).or_insert(0) += 1;
+            std::mem::swap(&mut b, &mut d);
+            endpoints[i] = (a, b);
+            endpoints[j] = (c, d);
+            swapped += 1;
+        }
+
+        let mut network = InteractionNetwork::new();
+        for node in self.nodes() {
+            network.add_node(node);
+        }
+        network.node_attributes = self.node_attributes.clone();
+        for (edge, &(source, target)) in self.edges().iter().zip(&endpoints) {
+            network.add_interaction(ProteinInteraction {
+                source: self.node_name(source).to_string(),
+                target: self.node_name(target).to_string(),
+                ..edge.clone()
+            });
+        }
+        network
+    }
+}
+
+/// Observed value of a statistic against its distribution over random networks.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Significance {
+    pub observed: f64,
+    pub null_mean: f64,
+    pub null_sd: f64,
+    /// `None` when the null distribution has no spread.
+    pub z_score: Option<f64>,
+    /// Empirical `P(null >= observed)`, counting the observed network.
+    pub p_greater: f64,
+    /// Empirical `P(null <= observed)`.
+    pub p_less: f64,
+    pub null: Vec<f64>,
+}
+
+impl Significance {
+    fn new(observed: f64, null: Vec<f64>) -> Self {
+        let n = null.len() as f64;
+        let mean = null.iter().sum::<f64>() / n;
+        let variance = null.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0).max(1.0);
+        let sd = variance.sqrt();
+        let empirical = |count: usize| (count + 1) as f64 / (null.len() + 1) as f64;
+        Self {
+            observed,
+            null_mean: mean,
+            null_sd: sd,
+            z_score: (sd > 0.0).then(|| (observed - mean) / sd),
+            p_greater: empirical(null.iter().filter(|&&x| x >= observed).count()),
+            p_less: empirical(null.iter().filter(|&&x| x <= observed).count()),
+            null,
+        }
+    }
+
+    /// Two-sided empirical p-value.
+    pub fn p_value(&self) -> f64 {
+        (2.0 * self.p_greater.min(self.p_less)).min(1.0)
+    }
+}
+
+/// Recomputes network statistics over degree-preserving random networks.
+#[derive(Debug, Clone)]
+pub struct PermutationTest {
+    pub permutations: usize,
+    pub swaps_per_edge: usize,
+    /// Seed for the random networks, so runs are reproducible.
+    pub seed: u64,
+}
+
+impl Default for PermutationTest {
+    fn default() -> Self {
+        Self::new(DEFAULT_PERMUTATIONS)
+    }
+}
+
+impl PermutationTest {
+    pub fn new(permutations: usize) -> Self {
+        Self {
+            permutations,
+            swaps_per_edge: DEFAULT_SWAPS_PER_EDGE,
+            seed: 0,
+        }
+    }
+
+    pub fn with_swaps_per_edge(mut self, swaps: usize) -> Self {
+        self.swaps_per_edge = swaps;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Significance of one scalar statistic.
+    pub fn run<F>(&self, network: &InteractionNetwork, statistic: F) -> Result<Significance>
+    where
+        F: Fn(&InteractionNetwork) -> f64,
+    {
+        let mut results = self.run_all(network, |n| vec![statistic(n)])?;
+        results
+            .pop()
+            .ok_or_else(|| MCL1Error::MissingParameter("statistic".to_string()))
+    }
+
+    /// Significance of each entry of a fixed-length statistic vector, such
+    /// as one score per gene set, all from the same random networks.
+    pub fn run_all<F>(&self, network: &InteractionNetwork, statistic: F) -> Result<Vec<Significance>>
+    where
+        F: Fn(&InteractionNetwork) -> Vec<f64>,
+    {
+        if self.permutations == 0 {
+            return Err(MCL1Error::InvalidParameter {
+                name: "permutations".to_string(),
+                message: "at least one permutation is needed".to_string(),
+            });
+        }
+        let observed = statistic(network);
+        let mut null: Vec<Vec<f64>> = vec![Vec::with_capacity(self.permutations); observed.len()];
+        let mut rng = StdRng::seed_from_u64(self.seed);
+
+        for _ in 0..self.permutations {
+            let values = statistic(&network.randomized(self.swaps_per_edge, &mut rng));
+            if values.len() != observed.len() {
+                return Err(MCL1Error::InvalidParameter {
+                    name: "statistic".to_string(),
+                    message: format!(
+                        "returned {} values for a random network but {} for the observed one",
+                        values.len(),
+                        observed.len()
+                    ),
+                });
+            }
+            for (null, value) in null.iter_mut().zip(values) {
+                null.push(value);
+            }
+        }
+
+        Ok(observed
+            .into_iter()
+            .zip(null)
+            .map(|(observed, null)| Significance::new(observed, null))
+            .collect())
+    }
+}
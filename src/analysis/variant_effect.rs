@@ -0,0 +1,521 @@
+//! Protein variant annotation from HGVS `p.` notation
+//!
+//! Variants are applied to a `Protein` to report the domains and
+//! modification sites they hit. Those hits are then turned into reduced or
+//! unknown effects on the protein's interactions. Residues are 1-based,
+//! as in HGVS.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+use crate::analysis::alignment::SubstitutionMatrix;
+use crate::analysis::physicochemical::{self, PkaSet};
+use crate::error::{MCL1Error, Result};
+use crate::models::protein::{Domain, InteractionType, Protein};
+use crate::models::ptm::{ModificationType, PtmSite};
+
+/// Helices lining the BH3-binding groove of anti-apoptotic BCL-2 proteins,
+/// plus the BH3 motif through which partners bind it.
+pub const BINDING_GROOVE_DOMAINS: &[&str] = &["BH1", "BH2", "BH3"];
+
+const THREE_LETTER_CODES: &[(&str, char)] = &[
+    ("Ala", 'A'),
+    ("Arg", 'R'),
+    ("Asn", 'N'),
+    ("Asp", 'D'),
+    ("Cys", 'C'),
+    ("Gln", 'Q'),
+    ("Glu", 'E'),
+    ("Gly", 'G'),
+    ("His", 'H'),
+    ("Ile", 'I'),
+    ("Leu", 'L'),
+    ("Lys", 'K'),
+    ("Met", 'M'),
+    ("Phe", 'F'),
+    ("Pro", 'P'),
+    ("Ser", 'S'),
+    ("Thr", 'T'),
+    ("Trp", 'W'),
+    ("Tyr", 'Y'),
+    ("Val", 'V'),
+    ("Sec", 'U'),
+    ("Pyl", 'O'),
+    ("Xaa", 'X'),
+    ("Ter", '*'),
+];
+
+/// Affinity kept after a substitution the matrix scores as conservative.
+const CONSERVATIVE_FACTOR: f64 = 0.75;
+/// Affinity kept after a non-conservative substitution.
+const NON_CONSERVATIVE_FACTOR: f64 = 0.5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VariantKind {
+    Missense,
+    Nonsense,
+    Synonymous,
+    Deletion,
+    Duplication,
+    Insertion,
+    DelIns,
+    Frameshift,
+}
+
+/// A protein-level variant; `start..=end` are the reference residues it
+/// names, and for insertions the two flanking residues.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProteinVariant {
+    pub hgvs: String,
+    pub kind: VariantKind,
+    pub start: usize,
+    pub end: usize,
+    /// Reference residues named at `start` and `end`.
+    pub reference: Vec<char>,
+    /// Replacement or inserted residues; empty for deletions and truncations.
+    pub alternate: String,
+}
+
+fn hgvs_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(r"^([A-Z](?:[a-z]{2})?)(\d+)(?:_([A-Z](?:[a-z]{2})?)(\d+))?(.*)$").expect("valid HGVS pattern")
+    })
+}
+
+/// Converts one- or three-letter residue codes to a one-letter string.
+fn residues(codes: &str) -> Option<String> {
+    let mut residues = String::new();
+    let mut rest = codes;
+    while !rest.is_empty() {
+        if let Some((code, residue)) = THREE_LETTER_CODES.iter().find(|(code, _)| rest.starts_with(code)) {
+            residues.push(*residue);
+            rest = &rest[code.len()..];
+        } else {
+            let residue = rest.chars().next()?;
+            if !(residue.is_ascii_uppercase() || residue == '*') {
+                return None;
+            }
+            residues.push(residue);
+            rest = &rest[1..];
+        }
+    }
+    Some(residues)
+}
+
+impl ProteinVariant {
+    /// Parses notation such as `p.R263A`, `p.(Arg263Ala)`, `p.S159*`,
+    /// `p.G217_D218del`, `p.K194_K197delinsA`, `p.A209_L210insRR`,
+    /// `p.R263dup` or `p.Arg263GlyfsTer12`. A leading `NP_...:` or gene
+    /// prefix is ignored.
+    pub fn parse(hgvs: &str) -> Result<Self> {
+        let invalid = |message: &str| MCL1Error::parse("HGVS", 1, 0, format!("'{}': {}", hgvs, message));
+
+        let notation = hgvs.trim();
+        let notation = notation.rsplit_once(':').map_or(notation, |(_, n)| n);
+        let notation = notation
+            .strip_prefix("p.")
+            .ok_or_else(|| invalid("expected protein notation starting with 'p.'"))?;
+        let notation = notation.trim_start_matches('(').trim_end_matches(')');
+
+        let captures = hgvs_pattern()
+            .captures(notation)
+            .ok_or_else(|| invalid("expected a residue and position"))?;
+        let residue = |index: usize| captures.get(index).and_then(|m| residues(m.as_str()));
+        let position = |index: usize| captures.get(index).and_then(|m| m.as_str().parse::<usize>().ok());
+
+        let start = position(2)
+            .filter(|p| *p > 0)
+            .ok_or_else(|| invalid("positions are 1-based"))?;
+        let end = position(4).unwrap_or(start);
+        if end < start {
+            return Err(invalid("range end precedes its start"));
+        }
+        let mut reference: Vec<char> = residue(1)
+            .ok_or_else(|| invalid("unknown reference residue"))?
+            .chars()
+            .collect();
+        if captures.get(3).is_some() {
+            reference.extend(residue(3).ok_or_else(|| invalid("unknown reference residue"))?.chars());
+        }
+
+        let rest = &captures[5];
+        let (kind, alternate) = if rest == "=" {
+            (VariantKind::Synonymous, reference[0].to_string())
+        } else if rest == "del" {
+            (VariantKind::Deletion, String::new())
+        } else if rest == "dup" {
+            (VariantKind::Duplication, String::new())
+        } else if let Some(inserted) = rest.strip_prefix("delins") {
+            (
+                VariantKind::DelIns,
+                residues(inserted).ok_or_else(|| invalid("bad inserted residues"))?,
+            )
+        } else if let Some(inserted) = rest.strip_prefix("ins") {
+            if end != start + 1 {
+                return Err(invalid("insertions need two adjacent flanking residues"));
+            }
+            (
+                VariantKind::Insertion,
+                residues(inserted).ok_or_else(|| invalid("bad inserted residues"))?,
+            )
+        } else if rest.contains("fs") {
+            (VariantKind::Frameshift, String::new())
+        } else {
+            let alternate = residues(rest)
+                .filter(|r| r.len() == 1 && end == start)
+                .ok_or_else(|| invalid("unsupported variant description"))?;
+            match alternate.as_str() {
+                "*" => (VariantKind::Nonsense, String::new()),
+                a if a.starts_with(reference[0]) => (VariantKind::Synonymous, alternate),
+                _ => (VariantKind::Missense, alternate),
+            }
+        };
+
+        Ok(Self {
+            hgvs: hgvs.trim().to_string(),
+            kind,
+            start,
+            end,
+            reference,
+            alternate,
+        })
+    }
+
+    /// True if the variant drops every residue from `start` onwards.
+    pub fn is_truncating(&self) -> bool {
+        matches!(self.kind, VariantKind::Nonsense | VariantKind::Frameshift)
+    }
+
+    /// Residues whose identity the variant changes, as an inclusive range
+    /// clipped to `length`; `None` for synonymous variants.
+    fn hit_range(&self, length: usize) -> Option<(usize, usize)> {
+        match self.kind {
+            VariantKind::Synonymous => None,
+            _ if self.is_truncating() => Some((self.start, length.max(self.start))),
+            _ => Some((self.start, self.end)),
+        }
+    }
+
+    fn check_reference(&self, protein: &Protein) -> Result<()> {
+        let sequence: Vec<char> = protein.sequence.chars().collect();
+        let positions = if self.start == self.end {
+            vec![self.start]
+        } else {
+            vec![self.start, self.end]
+        };
+        let invalid = |message: String| MCL1Error::InvalidSite {
+            protein: protein.id.clone(),
+            site: self.hgvs.clone(),
+            message,
+        };
+        if self.reference.is_empty() {
+            return Err(invalid("no reference residue given".to_string()));
+        }
+        if let Some(position) = positions.iter().find(|&&p| p == 0 || p > sequence.len()) {
+            return Err(invalid(format!(
+                "position {} is outside the {}-residue sequence",
+                position,
+                sequence.len()
+            )));
+        }
+        for (position, expected) in positions.into_iter().zip(&self.reference) {
+            let actual = sequence[position - 1];
+            if !actual.eq_ignore_ascii_case(expected) {
+                return Err(invalid(format!("sequence has '{}' at {}", actual, position)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Applies the variant to `sequence`, returning the new sequence and
+    /// where the change starts, how many residues it removed and how many
+    /// it added.
+    fn edit(&self, sequence: &str) -> (String, usize, usize, usize) {
+        let mut residues: Vec<char> = sequence.chars().collect();
+        let from = self.start - 1;
+        let to = self.end.min(residues.len());
+        let alternate: Vec<char> = self.alternate.chars().collect();
+        let (removed, added) = match self.kind {
+            VariantKind::Synonymous => (0, 0),
+            VariantKind::Missense | VariantKind::DelIns => {
+                residues.splice(from..to, alternate.iter().copied());
+                (to - from, alternate.len())
+            }
+            VariantKind::Deletion => {
+                residues.drain(from..to);
+                (to - from, 0)
+            }
+            VariantKind::Duplication => {
+                let copy: Vec<char> = residues[from..to].to_vec();
+                residues.splice(to..to, copy);
+                return (residues.into_iter().collect(), to, 0, to - from);
+            }
+            VariantKind::Insertion => {
+                residues.splice(self.start..self.start, alternate.iter().copied());
+                return (residues.into_iter().collect(), self.start, 0, alternate.len());
+            }
+            VariantKind::Nonsense | VariantKind::Frameshift => {
+                let removed = residues.len() - from;
+                residues.truncate(from);
+                (removed, 0)
+            }
+        };
+        (residues.into_iter().collect(), from, removed, added)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ImpactEffect {
+    /// Affinity scaled down by a known factor.
+    Reduced,
+    /// The variant hits the protein where it may matter, but no rule says how.
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InteractionImpact {
+    pub partner_id: String,
+    pub interaction_type: InteractionType,
+    pub affinity_before: f64,
+    /// New affinity for `Reduced` impacts.
+    pub affinity_after: Option<f64>,
+    pub effect: ImpactEffect,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VariantEffect {
+    pub protein_id: String,
+    pub variant: ProteinVariant,
+    pub hit_domains: Vec<Domain>,
+    pub hit_ptm_sites: Vec<PtmSite>,
+    pub interactions: Vec<InteractionImpact>,
+    /// True if a binding-groove domain is hit.
+    pub disrupts_binding_groove: bool,
+}
+
+/// Annotates variants against a protein's domains, sites and interactions.
+#[derive(Debug, Clone)]
+pub struct VariantAnnotator {
+    /// Domain names whose disruption reduces binding affinity.
+    pub binding_domains: Vec<String>,
+    pub matrix: SubstitutionMatrix,
+}
+
+impl Default for VariantAnnotator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VariantAnnotator {
+    pub fn new() -> Self {
+        Self {
+            binding_domains: BINDING_GROOVE_DOMAINS.iter().map(|d| d.to_string()).collect(),
+            matrix: SubstitutionMatrix::BLOSUM62,
+        }
+    }
+
+    pub fn with_binding_domains(mut self, domains: &[&str]) -> Self {
+        self.binding_domains = domains.iter().map(|d| d.to_string()).collect();
+        self
+    }
+
+    /// Fraction of affinity kept when a binding domain is hit: truncations
+    /// and indels abolish binding, substitutions keep more the more
+    /// conservative the matrix considers them.
+    fn binding_factor(&self, variant: &ProteinVariant) -> f64 {
+        match variant.kind {
+            VariantKind::Missense if self.matrix.score(variant.reference[0], variant.alternate_residue()) > 0 => {
+                CONSERVATIVE_FACTOR
+            }
+            VariantKind::Missense => NON_CONSERVATIVE_FACTOR,
+            _ => 0.0,
+        }
+    }
+
+    pub fn annotate(&self, protein: &Protein, variant: &ProteinVariant) -> Result<VariantEffect> {
+        variant.check_reference(protein)?;
+
+        let range = variant.hit_range(protein.sequence.len());
+        let hits = |start: usize, end: usize| match (range, variant.kind) {
+            // An insertion only lands inside a feature spanning both flanks.
+            (Some((from, to)), VariantKind::Insertion) => start <= from && to <= end,
+            (Some((from, to)), _) => start <= to && from <= end,
+            (None, _) => false,
+        };
+        let hit_domains: Vec<Domain> = protein
+            .domains
+            .iter()
+            .filter(|d| hits(d.start, d.end))
+            .cloned()
+            .collect();
+        let hit_ptm_sites: Vec<PtmSite> = protein
+            .ptm_sites
+            .iter()
+            .filter(|s| variant.kind != VariantKind::Insertion && hits(s.position, s.position))
+            .cloned()
+            .collect();
+        let groove: Vec<&Domain> = hit_domains
+            .iter()
+            .filter(|d| self.binding_domains.iter().any(|b| b.eq_ignore_ascii_case(&d.name)))
+            .collect();
+
+        let mut interactions = Vec::new();
+        for interaction in &protein.interactions {
+            let impact = |effect, affinity_after, reason: String| InteractionImpact {
+                partner_id: interaction.partner_id.clone(),
+                interaction_type: interaction.interaction_type.clone(),
+                affinity_before: interaction.binding_affinity,
+                affinity_after,
+                effect,
+                reason,
+            };
+            let writes_site = hit_ptm_sites.iter().find(|site| {
+                site.enzyme.as_deref().is_some_and(|e| {
+                    e.eq_ignore_ascii_case(&interaction.partner_id) || e.eq_ignore_ascii_case(&interaction.partner_name)
+                })
+            });
+
+            match (&interaction.interaction_type, writes_site) {
+                (InteractionType::Modification, Some(site)) if variant.is_mimetic(site) => interactions.push(impact(
+                    ImpactEffect::Unknown,
+                    None,
+                    format!("{} becomes a mimetic", site.label()),
+                )),
+                (InteractionType::Modification, Some(site)) => interactions.push(impact(
+                    ImpactEffect::Reduced,
+                    Some(0.0),
+                    format!("{} site lost", site.label()),
+                )),
+                (InteractionType::Binding | InteractionType::Inhibition, _) if !groove.is_empty() => {
+                    let names: Vec<&str> = groove.iter().map(|d| d.name.as_str()).collect();
+                    interactions.push(impact(
+                        ImpactEffect::Reduced,
+                        Some(interaction.binding_affinity * self.binding_factor(variant)),
+                        format!("hits {}", names.join(", ")),
+                    ))
+                }
+                _ if !hit_domains.is_empty() => {
+                    let names: Vec<&str> = hit_domains.iter().map(|d| d.name.as_str()).collect();
+                    interactions.push(impact(
+                        ImpactEffect::Unknown,
+                        None,
+                        format!("hits {}", names.join(", ")),
+                    ))
+                }
+                _ => {}
+            }
+        }
+
+        Ok(VariantEffect {
+            protein_id: protein.id.clone(),
+            variant: variant.clone(),
+            disrupts_binding_groove: !groove.is_empty(),
+            hit_domains,
+            hit_ptm_sites,
+            interactions,
+        })
+    }
+
+    /// Annotates the variant and returns the mutated protein: new sequence,
+    /// recomputed mass and pI, domains and sites shifted past indels, sites
+    /// whose residue changed removed, and reduced affinities applied.
+    pub fn apply(&self, protein: &Protein, variant: &ProteinVariant) -> Result<(Protein, VariantEffect)> {
+        let effect = self.annotate(protein, variant)?;
+        let (sequence, at, removed, added) = variant.edit(&protein.sequence);
+        let shift = |position: usize| -> Option<usize> {
+            if position <= at {
+                Some(position)
+            } else if position > at + removed {
+                Some(position - removed + added)
+            } else {
+                None
+            }
+        };
+
+        let mut mutated = protein.clone();
+        mutated.molecular_weight = physicochemical::average_mass(&sequence);
+        mutated.isoelectric_point = physicochemical::isoelectric_point(&sequence, &PkaSet::default());
+        mutated.domains = protein
+            .domains
+            .iter()
+            .filter_map(|domain| {
+                let start = shift(domain.start).unwrap_or(at + 1);
+                let end = shift(domain.end).unwrap_or(at + added).min(sequence.len());
+                (start <= end).then(|| Domain {
+                    start,
+                    end,
+                    ..domain.clone()
+                })
+            })
+            .collect();
+        mutated.ptm_sites = protein
+            .ptm_sites
+            .iter()
+            .filter(|site| !effect.hit_ptm_sites.contains(site))
+            .filter_map(|site| {
+                shift(site.position).map(|position| PtmSite {
+                    position,
+                    ..site.clone()
+                })
+            })
+            .collect();
+        mutated.sequence = sequence;
+
+        for impact in &effect.interactions {
+            let interaction = mutated
+                .interactions
+                .iter_mut()
+                .find(|i| i.partner_id == impact.partner_id && i.interaction_type == impact.interaction_type);
+            if let Some(interaction) = interaction {
+                if let Some(affinity) = impact.affinity_after {
+                    interaction.binding_affinity = affinity;
+                }
+                if !interaction.evidence.is_empty() {
+                    interaction.evidence.push_str("; ");
+                }
+                interaction
+                    .evidence
+                    .push_str(&format!("{}: {:?} ({})", variant.hgvs, impact.effect, impact.reason));
+            }
+        }
+
+        Ok((mutated, effect))
+    }
+
+    /// Parses and annotates a list of HGVS strings against one protein.
+    pub fn annotate_all(&self, protein: &Protein, hgvs: &[&str]) -> Result<Vec<VariantEffect>> {
+        hgvs.iter()
+            .map(|notation| self.annotate(protein, &ProteinVariant::parse(notation)?))
+            .collect()
+    }
+}
+
+impl ProteinVariant {
+    fn alternate_residue(&self) -> char {
+        self.alternate.chars().next().unwrap_or('*')
+    }
+
+    /// Serine/threonine to aspartate/glutamate mimics phosphorylation.
+    fn is_mimetic(&self, site: &PtmSite) -> bool {
+        self.kind == VariantKind::Missense
+            && site.modification == ModificationType::Phosphorylation
+            && matches!(self.alternate_residue(), 'D' | 'E')
+    }
+}
+
+/// Variant row as stored on disk, e.g. `MCL1,p.R263A`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VariantRecord {
+    pub gene: String,
+    pub hgvs: String,
+}
+
+impl VariantRecord {
+    pub fn variant(&self) -> Result<ProteinVariant> {
+        ProteinVariant::parse(&self.hgvs)
+    }
+}
@@ -0,0 +1,18 @@
+use std::process;
+
+use clap::Parser;
+use mcl1_regulator::interactive::InteractiveAnalyzer;
+
+/// Interactive MCL1 interaction analysis session
+#[derive(Parser)]
+#[command(name = "interactive-analyzer", version)]
+struct Args {}
+
+fn main() {
+    Args::parse();
+
+    if let Err(e) = InteractiveAnalyzer::new().run() {
+        eprintln!("Interactive session failed: {}", e);
+        process::exit(1);
+    }
+}
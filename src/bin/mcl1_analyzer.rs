@@ -0,0 +1,46 @@
+use std::process;
+
+use clap::Parser;
+use mcl1_regulator::utils::results_formatter::format_analysis_report;
+use mcl1_regulator::MCL1Analyzer;
+
+/// Analyze MCL1 protein interactions from a CSV file
+#[derive(Parser)]
+#[command(name = "mcl1-analyzer", version)]
+struct Args {
+    /// Interaction CSV with source,target,interaction_type,confidence columns
+    input: String,
+
+    /// Minimum cardiotoxicity score for a target to be reported
+    #[arg(short, long, default_value_t = 0.5)]
+    threshold: f64,
+
+    /// Print the report as JSON instead of text
+    #[arg(long)]
+    json: bool,
+}
+
+fn main() {
+    let args = Args::parse();
+    let analyzer = MCL1Analyzer::new().with_cardiotoxicity_threshold(args.threshold);
+
+    let report = match analyzer.analyze_interactions(&args.input) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("Failed to analyze {}: {}", args.input, e);
+            process::exit(1);
+        }
+    };
+
+    if args.json {
+        match serde_json::to_string_pretty(&report) {
+            Ok(json) => println!("{}", json),
+            Err(e) => {
+                eprintln!("Failed to serialize report: {}", e);
+                process::exit(1);
+            }
+        }
+    } else {
+        print!("{}", format_analysis_report(&report));
+    }
+}
@@ -0,0 +1,60 @@
+use std::fs;
+use std::path::Path;
+use std::process;
+
+use clap::Parser;
+use mcl1_regulator::analysis::pathway_prediction::PathwayPredictionInput;
+use mcl1_regulator::utils::results_formatter::format_prediction_result;
+use mcl1_regulator::PathwayPredictor;
+
+/// Predict metabolic pathway activation from MCL1 interaction data
+#[derive(Parser)]
+#[command(name = "pathway-predictor", version)]
+struct Args {
+    /// JSON file with `mcl1_interactions` and `metabolic_data`
+    #[arg(short, long)]
+    input: String,
+
+    /// File the predictions are written to
+    #[arg(short, long)]
+    output: String,
+}
+
+fn main() {
+    let args = Args::parse();
+
+    let input = match PathwayPredictionInput::from_json_file(&args.input) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Failed to read {}: {}", args.input, e);
+            process::exit(1);
+        }
+    };
+
+    let predictor = PathwayPredictor::new();
+    let result = match predictor.predict_pathways(&input.mcl1_interactions, &input.metabolic_data) {
+        Ok(result) => result,
+        Err(e) => {
+            eprintln!("Prediction failed: {}", e);
+            process::exit(1);
+        }
+    };
+
+    if let Some(parent) = Path::new(&args.output).parent() {
+        if let Err(e) = fs::create_dir_all(parent) {
+            eprintln!("Failed to create {}: {}", parent.display(), e);
+            process::exit(1);
+        }
+    }
+
+    if let Err(e) = fs::write(&args.output, format_prediction_result(&result)) {
+        eprintln!("Failed to write {}: {}", args.output, e);
+        process::exit(1);
+    }
+
+    println!(
+        "Predicted {} pathways, written to {}",
+        result.predicted_pathways.len(),
+        args.output
+    );
+}
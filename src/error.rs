@@ -0,0 +1,101 @@
+use std::fmt;
+use std::io;
+
+/// Errors returned by every fallible public API in the crate.
+#[derive(Debug)]
+pub enum MCL1Error {
+    Io(io::Error),
+    /// Malformed input; `line` and `column` are 1-based, `column` counts
+    /// characters for text formats and fields for delimited ones.
+    Parse {
+        file: String,
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    MissingParameter(String),
+    UnknownProtein(String),
+    /// A modification site that does not fit the protein's sequence.
+    InvalidSite {
+        protein: String,
+        site: String,
+        message: String,
+    },
+    InvalidScore {
+        name: String,
+        value: f64,
+        min: f64,
+        max: f64,
+    },
+    /// An option or argument that is unusable for a reason other than range.
+    InvalidParameter {
+        name: String,
+        message: String,
+    },
+}
+
+pub type Result<T> = std::result::Result<T, MCL1Error>;
+
+impl MCL1Error {
+    pub fn parse(file: &str, line: usize, column: usize, message: impl Into<String>) -> Self {
+        MCL1Error::Parse {
+            file: file.to_string(),
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    pub fn from_json(file: &str, error: serde_json::Error) -> Self {
+        MCL1Error::parse(file, error.line(), error.column(), error.to_string())
+    }
+
+    /// Returns `value` if it lies in `[min, max]`, or an `InvalidScore` error.
+    pub fn check_range(name: &str, value: f64, min: f64, max: f64) -> Result<f64> {
+        if value >= min && value <= max {
+            Ok(value)
+        } else {
+            Err(MCL1Error::InvalidScore {
+                name: name.to_string(),
+                value,
+                min,
+                max,
+            })
+        }
+    }
+}
+
+impl fmt::Display for MCL1Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MCL1Error::Io(e) => write!(f, "I/O error: {}", e),
+            MCL1Error::Parse { file, line, column, message } => {
+                write!(f, "{}:{}:{}: {}", file, line, column, message)
+            }
+            MCL1Error::MissingParameter(name) => write!(f, "missing model parameter '{}'", name),
+            MCL1Error::UnknownProtein(id) => write!(f, "unknown protein '{}'", id),
+            MCL1Error::InvalidSite { protein, site, message } => {
+                write!(f, "invalid site {} on {}: {}", site, protein, message)
+            }
+            MCL1Error::InvalidScore { name, value, min, max } => {
+                write!(f, "{} = {} is outside [{}, {}]", name, value, min, max)
+            }
+            MCL1Error::InvalidParameter { name, message } => write!(f, "invalid {}: {}", name, message),
+        }
+    }
+}
+
+impl std::error::Error for MCL1Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            MCL1Error::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for MCL1Error {
+    fn from(error: io::Error) -> Self {
+        MCL1Error::Io(error)
+    }
+}
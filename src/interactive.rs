@@ -0,0 +1,131 @@
+use std::io::{self, BufRead, Write};
+
+use crate::analysis::interaction_network::InteractionNetwork;
+use crate::mcl1_analyzer::{AnalysisReport, MCL1Analyzer};
+
+const HELP: &str = "Commands:
+  load <file>          Load an interaction CSV and analyze it
+  summary              Show the analysis summary
+  partners             List direct MCL1 partners
+  pathways             List predicted metabolic pathways
+  targets              List flagged drug targets
+  neighbors <protein>  List interaction partners of a protein
+  degree <protein>     Show the degree of a protein
+  help                 Show this message
+  quit                 Leave the session";
+
+/// Line-oriented analysis session over a loaded interaction network.
+#[derive(Default)]
+pub struct InteractiveAnalyzer {
+    analyzer: MCL1Analyzer,
+    network: Option<InteractionNetwork>,
+    report: Option<AnalysisReport>,
+}
+
+impl InteractiveAnalyzer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs the session on stdin/stdout until `quit` or end of input.
+    pub fn run(&mut self) -> io::Result<()> {
+        let stdin = io::stdin();
+        let stdout = io::stdout();
+        self.run_with(stdin.lock(), stdout.lock())
+    }
+
+    pub fn run_with<R: BufRead, W: Write>(&mut self, input: R, mut output: W) -> io::Result<()> {
+        writeln!(output, "MCL1 interactive analyzer. Type 'help' for commands.")?;
+
+        for line in input.lines() {
+            let line = line?;
+            let mut parts = line.split_whitespace();
+            let command = match parts.next() {
+                Some(command) => command,
+                None => continue,
+            };
+            let argument = parts.next();
+
+            match command {
+                "quit" | "exit" => break,
+                "help" => writeln!(output, "{}", HELP)?,
+                "load" => match argument {
+                    Some(path) => self.load(path, &mut output)?,
+                    None => writeln!(output, "Usage: load <file>")?,
+                },
+                "summary" => {
+                    if let Some(report) = self.report(&mut output)? {
+                        write!(output, "{}", report.summary())?;
+                    }
+                }
+                "partners" => {
+                    if let Some(report) = self.report(&mut output)? {
+                        writeln!(output, "{}", report.mcl1_partners.join(", "))?;
+                    }
+                }
+                "pathways" => {
+                    if let Some(report) = self.report(&mut output)? {
+                        for pathway in &report.pathway_prediction.predicted_pathways {
+                            writeln!(output, "{} ({:.3})", pathway.id, pathway.mcl1_interaction_score)?;
+                        }
+                    }
+                }
+                "targets" => {
+                    if let Some(report) = self.report(&mut output)? {
+                        for target in &report.drug_targets {
+                            writeln!(
+                                output,
+                                "{} (cardiotoxicity risk: {:.2})",
+                                target.target_protein, target.cardiotoxicity_risk
+                            )?;
+                        }
+                    }
+                }
+                "neighbors" | "degree" => match (&self.network, argument) {
+                    (None, _) => writeln!(output, "No network loaded. Use 'load <file>' first.")?,
+                    (Some(_), None) => writeln!(output, "Usage: {} <protein>", command)?,
+                    (Some(network), Some(protein)) => {
+                        if command == "degree" {
+                            writeln!(output, "{}", network.get_degree(protein))?;
+                        } else {
+                            let mut neighbors = network.get_neighbors(protein);
+                            neighbors.sort();
+                            neighbors.dedup();
+                            let names: Vec<&str> = neighbors.iter().map(|n| n.as_str()).collect();
+                            writeln!(output, "{}", names.join(", "))?;
+                        }
+                    }
+                },
+                _ => writeln!(output, "Unknown command: {}. Type 'help' for commands.", command)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn load<W: Write>(&mut self, path: &str, output: &mut W) -> io::Result<()> {
+        match self.analyzer.load_network(path) {
+            Ok(network) => match self.analyzer.analyze_network(&network, path) {
+                Ok(report) => {
+                    writeln!(
+                        output,
+                        "Loaded {} interactions between {} proteins",
+                        report.total_interactions, report.total_proteins
+                    )?;
+                    self.network = Some(network);
+                    self.report = Some(report);
+                }
+                Err(e) => writeln!(output, "Failed to analyze {}: {}", path, e)?,
+            },
+            Err(e) => writeln!(output, "Failed to load {}: {}", path, e)?,
+        }
+        Ok(())
+    }
+
+    fn report<W: Write>(&self, output: &mut W) -> io::Result<Option<&AnalysisReport>> {
+        if self.report.is_none() {
+            writeln!(output, "No network loaded. Use 'load <file>' first.")?;
+        }
+        Ok(self.report.as_ref())
+    }
+}
@@ -1,14 +1,23 @@
 // ! MCL1-Regulator: A Rust-based computational tool for analyzing MCL1 protein interactions
 // ! and predicting cancer cell survival pathways.
 
+/// Network, pathway and cardiotoxicity analyses
+pub mod analysis;
+/// Crate-wide error type
+pub mod error;
+/// Line-oriented interactive analysis session
+pub mod interactive;
 /// Main module for MCL1 analysis functionality
 pub mod mcl1_analyzer;
-/// Module for metabolic pathway prediction
-pub mod pathway_predictor;
+/// Core biological data models
+pub mod models;
+/// Loading, formatting and numeric helpers
+pub mod utils;
 
 /// Re-export key types and functions for easy access
-pub use mcl1_analyzer::MCL1Analyzer;
-pub use pathway_predictor::PathwayPredictor;
+pub use analysis::pathway_prediction::PathwayPredictor;
+pub use error::{MCL1Error, Result};
+pub use mcl1_analyzer::{AnalysisReport, MCL1Analyzer};
 
 #[cfg(test)]
 mod tests {
@@ -17,12 +26,13 @@ mod tests {
     #[test]
     fn test_analyzer_creation() {
         let analyzer = MCL1Analyzer::new();
-        assert!(analyzer.is_some());
+        assert_eq!(analyzer.cardiotoxicity_predictor.threshold, 0.5);
     }
 
     #[test]
     fn test_predictor_creation() {
         let predictor = PathwayPredictor::new();
-        assert!(predictor.is_some());
+        let result = predictor.predict_pathways(&[], &Default::default()).unwrap();
+        assert!(result.predicted_pathways.is_empty());
     }
 }
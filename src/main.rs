@@ -1,86 +1,59 @@
-use std::env;
+use std::fs;
+use std::path::Path;
 use std::process;
 
-mod analyzer;
-mod predictor;
-mod interactive;
-
-use analyzer::MCL1Analyzer;
-use predictor::PathwayPredictor;
-use interactive::InteractiveAnalyzer;
+use clap::{Parser, Subcommand};
+use mcl1_regulator::analysis::pathway_prediction::PathwayPredictionInput;
+use mcl1_regulator::interactive::InteractiveAnalyzer;
+use mcl1_regulator::utils::results_formatter::{format_analysis_report, format_prediction_result};
+use mcl1_regulator::{MCL1Analyzer, MCL1Error, PathwayPredictor, Result};
+
+#[derive(Parser)]
+#[command(name = "mcl1-regulator", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Analyze MCL1 interactions
+    Analyze { input_file: String },
+    /// Predict metabolic pathways
+    Predict {
+        #[arg(short, long)]
+        input: String,
+        #[arg(short, long)]
+        output: String,
+    },
+    /// Launch interactive mode
+    Interactive,
+}
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-
-    if args.len() < 2 {
-        eprintln!("Usage: {} <command> [arguments]", args[0]);
-        eprintln!("Commands:");
-        eprintln!("  analyze <input_file> - Analyze MCL1 interactions");
-        eprintln!("  predict -i <input_file> -o <output_file> - Predict metabolic pathways");
-        eprintln!("  interactive - Launch interactive mode");
+    let cli = Cli::parse();
+
+    let result = match cli.command {
+        Command::Analyze { input_file } => MCL1Analyzer::new()
+            .analyze_interactions(&input_file)
+            .map(|report| print!("{}", format_analysis_report(&report))),
+        Command::Predict { input, output } => predict(&input, &output),
+        Command::Interactive => InteractiveAnalyzer::new().run().map_err(MCL1Error::from),
+    };
+
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
         process::exit(1);
     }
+}
 
-    let command = &args[1];
-
-    match command.as_str() {
-        "analyze" => {
-            if args.len() < 3 {
-                eprintln!("Usage: {} analyze <input_file>", args[0]);
-                process::exit(1);
-            }
-            let input_file = &args[2];
-            let analyzer = MCL1Analyzer::new();
-            analyzer.analyze_interactions(input_file);
-        }
-        "predict" => {
-            let mut input_file = String::new();
-            let mut output_file = String::new();
-            
-            let mut i = 2;
-            while i < args.len() {
-                match args[i].as_str() {
-                    "-i" => {
-                        if i + 1 < args.len() {
-                            input_file = args[i + 1].clone();
-                            i += 2;
-                        } else {
-                            eprintln!("Missing argument for -i");
-                            process::exit(1);
-                        }
-                    }
-                    "-o" => {
-                        if i + 1 < args.len() {
-                            output_file = args[i + 1].clone();
-                            i += 2;
-                        } else {
-                            eprintln!("Missing argument for -o");
-                            process::exit(1);
-                        }
-                    }
-                    _ => {
-                        eprintln!("Unknown option: {}", args[i]);
-                        process::exit(1);
-                    }
-                }
-            }
-
-            if input_file.is_empty() || output_file.is_empty() {
-                eprintln!("Usage: {} predict -i <input_file> -o <output_file>", args[0]);
-                process::exit(1);
-            }
+fn predict(input: &str, output: &str) -> Result<()> {
+    let input = PathwayPredictionInput::from_json_file(input)?;
+    let result = PathwayPredictor::new().predict_pathways(&input.mcl1_interactions, &input.metabolic_data)?;
 
-            let predictor = PathwayPredictor::new();
-            predictor.predict_pathways(&input_file, &output_file);
-        }
-        "interactive" => {
-            let interactive = InteractiveAnalyzer::new();
-            interactive.run();
-        }
-        _ => {
-            eprintln!("Unknown command: {}", command);
-            eprintln!("Use 'analyze', 'predict', or 'interactive'");
-            process::exit(1);
-        }
+    if let Some(parent) = Path::new(output).parent() {
+        fs::create_dir_all(parent)?;
     }
-}
\ No newline at end of file
+    fs::write(output, format_prediction_result(&result))?;
+    Ok(())
+}
@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::cardiotoxicity_prediction::CardiotoxicityPredictor;
+use crate::analysis::interaction_network::{InteractionNetwork, ProteinInteraction};
+use crate::analysis::pathway_prediction::{PathwayPredictionResult, PathwayPredictor};
+use crate::error::{MCL1Error, Result};
+use crate::models::metabolic_pathway::METABOLIC_GENE_SETS;
+use crate::models::DrugTarget;
+use crate::utils::data_loader::TableLoader;
+use crate::utils::results_formatter::format_summary;
+
+/// Protein the analysis is centred on.
+pub const MCL1_ID: &str = "MCL1";
+
+/// Everything the analyzer learns from one interaction file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalysisReport {
+    pub source_file: String,
+    pub total_interactions: usize,
+    pub total_proteins: usize,
+    pub mcl1_degree: usize,
+    pub mcl1_partners: Vec<String>,
+    pub pathway_prediction: PathwayPredictionResult,
+    pub drug_targets: Vec<DrugTarget>,
+}
+
+impl AnalysisReport {
+    pub fn active_pathway_count(&self) -> usize {
+        self.pathway_prediction.predicted_pathways.len()
+    }
+
+    pub fn summary(&self) -> String {
+        format_summary(
+            self.total_interactions,
+            self.active_pathway_count(),
+            self.drug_targets.len(),
+        )
+    }
+}
+
+/// Single entry point that runs the network, pathway and cardiotoxicity
+/// analyses over an interaction file.
+#[derive(Default)]
+pub struct MCL1Analyzer {
+    pub pathway_predictor: PathwayPredictor,
+    pub cardiotoxicity_predictor: CardiotoxicityPredictor,
+    pub loader: TableLoader,
+}
+
+impl MCL1Analyzer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_cardiotoxicity_threshold(mut self, threshold: f64) -> Self {
+        self.cardiotoxicity_predictor = CardiotoxicityPredictor::new(threshold);
+        self
+    }
+
+    /// Loads an interaction table with `source`, `target`,
+    /// `interaction_type` and `confidence` columns. Unlike the loader itself,
+    /// the analyzer is strict and fails on the first bad row.
+    pub fn load_network<P: AsRef<Path>>(&self, path: P) -> Result<InteractionNetwork> {
+        let load = self.loader.load_interactions(path)?;
+        if let Some(error) = load.row_errors.into_iter().next() {
+            return Err(error);
+        }
+
+        let mut network = InteractionNetwork::new();
+        for interaction in load.records {
+            network.add_interaction(interaction);
+        }
+        Ok(network)
+    }
+
+    pub fn analyze_interactions<P: AsRef<Path>>(&self, path: P) -> Result<AnalysisReport> {
+        let network = self.load_network(&path)?;
+        self.analyze_network(&network, &path.as_ref().display().to_string())
+    }
+
+    pub fn analyze_network(&self, network: &InteractionNetwork, source_file: &str) -> Result<AnalysisReport> {
+        if !network.contains_node(MCL1_ID) {
+            return Err(MCL1Error::UnknownProtein(MCL1_ID.to_string()));
+        }
+
+        let mcl1_network = network.get_subnetwork(&[MCL1_ID]);
+        let mut mcl1_partners: Vec<String> = network
+            .get_neighbors(MCL1_ID)
+            .into_iter()
+            .cloned()
+            .collect();
+        mcl1_partners.sort();
+        mcl1_partners.dedup();
+
+        let metabolic_data = Self::score_metabolic_pathways(&mcl1_network);
+        let mut pathway_prediction = self
+            .pathway_predictor
+            .predict_pathways(&mcl1_partners, &metabolic_data)?;
+        for pathway in &mut pathway_prediction.predicted_pathways {
+            if let Some((_, genes)) = METABOLIC_GENE_SETS.iter().find(|(id, _)| *id == pathway.id) {
+                pathway.genes_involved = genes.iter().map(|g| g.to_string()).collect();
+            }
+        }
+
+        // Orient MCL1 edges so the partner is always the candidate target.
+        let direct_edges: Vec<ProteinInteraction> = network
+            .edges()
+            .iter()
+            .filter_map(|edge| {
+                if edge.source == MCL1_ID {
+                    Some(edge.clone())
+                } else if edge.target == MCL1_ID {
+                    Some(ProteinInteraction {
+                        source: edge.target.clone(),
+                        target: edge.source.clone(),
+                        ..edge.clone()
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect();
+        let drug_targets = self.cardiotoxicity_predictor.predict_cardiotoxicity(&direct_edges);
+
+        Ok(AnalysisReport {
+            source_file: source_file.to_string(),
+            total_interactions: network.edge_count(),
+            total_proteins: network.node_count(),
+            mcl1_degree: network.get_degree(MCL1_ID),
+            mcl1_partners,
+            pathway_prediction,
+            drug_targets,
+        })
+    }
+
+    pub fn predict_metabolic_pathways<P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> Result<PathwayPredictionResult> {
+        Ok(self.analyze_interactions(path)?.pathway_prediction)
+    }
+
+    /// Scores each metabolic pathway by the mean confidence of the edges in
+    /// the MCL1 component that touch one of its marker genes.
+    fn score_metabolic_pathways(network: &InteractionNetwork) -> HashMap<String, f64> {
+        let mut scores = HashMap::new();
+
+        for (pathway_id, genes) in METABOLIC_GENE_SETS {
+            let touching: Vec<f64> = network
+                .edges()
+                .iter()
+                .filter(|edge| genes.contains(&edge.source.as_str()) || genes.contains(&edge.target.as_str()))
+                .map(|edge| edge.confidence)
+                .collect();
+
+            let score = if touching.is_empty() {
+                0.0
+            } else {
+                touching.iter().sum::<f64>() / touching.len() as f64
+            };
+            scores.insert(pathway_id.to_string(), score);
+        }
+
+        scores
+    }
+}
@@ -0,0 +1,111 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::protein::{Interaction, InteractionType};
+
+/// Whether an edge has a meaningful source-to-target orientation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Direction {
+    Undirected,
+    Directed,
+}
+
+/// Net effect of the source on the target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Sign {
+    Positive,
+    Negative,
+    Unsigned,
+}
+
+impl Sign {
+    /// Net sign of two effects in series: two inhibitions activate, and any
+    /// unsigned step leaves the chain unsigned.
+    pub fn then(self, next: Sign) -> Sign {
+        match (self, next) {
+            (Sign::Unsigned, _) | (_, Sign::Unsigned) => Sign::Unsigned,
+            (a, b) if a == b => Sign::Positive,
+            _ => Sign::Negative,
+        }
+    }
+}
+
+/// Canonical interaction edge that every other interaction struct in the
+/// crate converts into and out of.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InteractionEdge {
+    pub source: String,
+    pub target: String,
+    pub target_name: Option<String>,
+    pub kind: InteractionType,
+    /// Original type string, kept so conversions back are lossless.
+    pub label: String,
+    pub direction: Direction,
+    pub sign: Sign,
+    pub confidence: f64,
+    pub affinity: Option<f64>,
+    pub evidence: String,
+}
+
+impl InteractionEdge {
+    /// Creates an edge whose direction and sign follow from `kind`.
+    pub fn new(source: &str, target: &str, kind: InteractionType, confidence: f64) -> Self {
+        Self {
+            source: source.to_string(),
+            target: target.to_string(),
+            target_name: None,
+            label: kind.label().to_string(),
+            direction: kind.default_direction(),
+            sign: kind.default_sign(),
+            kind,
+            confidence,
+            affinity: None,
+            evidence: String::new(),
+        }
+    }
+
+    /// Creates an edge from a free-form type string such as `"inhibition"`.
+    pub fn from_label(source: &str, target: &str, label: &str, confidence: f64) -> Self {
+        let mut edge = Self::new(source, target, InteractionType::from_label(label), confidence);
+        edge.label = label.to_string();
+        edge
+    }
+
+    pub fn with_evidence(mut self, evidence: &str) -> Self {
+        self.evidence = evidence.to_string();
+        self
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.direction == Direction::Directed
+    }
+
+    /// Returns true if the edge touches `node` in either position.
+    pub fn involves(&self, node: &str) -> bool {
+        self.source == node || self.target == node
+    }
+
+    /// Builds an edge from an interaction recorded on the protein `owner_id`.
+    pub fn from_protein_interaction(owner_id: &str, interaction: &Interaction) -> Self {
+        let mut edge = Self::new(
+            owner_id,
+            &interaction.partner_id,
+            interaction.interaction_type.clone(),
+            1.0,
+        );
+        edge.target_name = Some(interaction.partner_name.clone());
+        edge.affinity = Some(interaction.binding_affinity);
+        edge.evidence = interaction.evidence.clone();
+        edge
+    }
+
+    /// Converts the edge into an interaction recorded on its source protein.
+    pub fn to_protein_interaction(&self) -> Interaction {
+        Interaction {
+            partner_id: self.target.clone(),
+            partner_name: self.target_name.clone().unwrap_or_else(|| self.target.clone()),
+            binding_affinity: self.affinity.unwrap_or(self.confidence),
+            interaction_type: self.kind.clone(),
+            evidence: self.evidence.clone(),
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::error::{MCL1Error, Result};
+use crate::models::interaction::InteractionEdge;
+use crate::models::protein::{InteractionType, Protein};
+
+/// Apoptotic role of a splice isoform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IsoformFunction {
+    AntiApoptotic,
+    ProApoptotic,
+    Unknown,
+}
+
+/// Known MCL1 splice isoforms: MCL1-L keeps the BCL-2 homology domains,
+/// MCL1-S skips exon 2 and MCL1-ES uses an alternative exon 1 donor.
+pub const MCL1_ISOFORMS: &[(&str, IsoformFunction)] = &[
+    ("MCL1-L", IsoformFunction::AntiApoptotic),
+    ("MCL1-S", IsoformFunction::ProApoptotic),
+    ("MCL1-ES", IsoformFunction::ProApoptotic),
+];
+
+/// One splice isoform: its own sequence, domains and interactions, all
+/// recorded on `protein`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Isoform {
+    pub name: String,
+    pub function: IsoformFunction,
+    pub protein: Protein,
+}
+
+impl Isoform {
+    pub fn new(name: &str, function: IsoformFunction, protein: Protein) -> Self {
+        Self {
+            name: name.to_string(),
+            function,
+            protein,
+        }
+    }
+
+    /// Records an interaction between this isoform and `partner`; the
+    /// confidence is stored as the interaction's binding affinity.
+    pub fn add_partner(&mut self, partner: &str, kind: InteractionType, confidence: f64) -> Result<()> {
+        let confidence = MCL1Error::check_range("confidence", confidence, 0.0, 1.0)?;
+        let edge = InteractionEdge::new(&self.protein.id, partner, kind, confidence);
+        self.protein.add_edges([&edge]);
+        Ok(())
+    }
+
+    /// The protein's interactions as edges from the isoform, with the
+    /// recorded binding affinity as confidence.
+    pub fn edges(&self) -> Vec<InteractionEdge> {
+        self.protein
+            .edges()
+            .into_iter()
+            .map(|mut edge| {
+                edge.source = self.name.clone();
+                edge.confidence = edge.affinity.map_or(edge.confidence, |affinity| affinity.clamp(0.0, 1.0));
+                edge
+            })
+            .collect()
+    }
+}
+
+/// A gene and its splice isoforms.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Gene {
+    pub symbol: String,
+    pub isoforms: Vec<Isoform>,
+}
+
+impl Gene {
+    pub fn new(symbol: &str) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            isoforms: Vec::new(),
+        }
+    }
+
+    pub fn add_isoform(&mut self, isoform: Isoform) {
+        self.isoforms.push(isoform);
+    }
+
+    pub fn isoform(&self, name: &str) -> Result<&Isoform> {
+        self.isoforms
+            .iter()
+            .find(|isoform| isoform.name == name)
+            .ok_or_else(|| MCL1Error::UnknownProtein(name.to_string()))
+    }
+
+    pub fn isoform_mut(&mut self, name: &str) -> Result<&mut Isoform> {
+        self.isoforms
+            .iter_mut()
+            .find(|isoform| isoform.name == name)
+            .ok_or_else(|| MCL1Error::UnknownProtein(name.to_string()))
+    }
+}
+
+/// Isoform expression row as stored on disk.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExpressionRecord {
+    pub isoform: String,
+    pub expression: f64,
+}
+
+/// Expression level per isoform name, in any non-negative unit (TPM, FPKM).
+#[derive(Debug, Clone, Default)]
+pub struct ExpressionTable {
+    pub levels: HashMap<String, f64>,
+}
+
+impl ExpressionTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_records(records: Vec<ExpressionRecord>) -> Self {
+        let mut table = Self::new();
+        for record in records {
+            table.levels.insert(record.isoform, record.expression);
+        }
+        table
+    }
+
+    pub fn set(&mut self, isoform: &str, expression: f64) {
+        self.levels.insert(isoform.to_string(), expression);
+    }
+
+    /// Expression of each of `gene`'s isoforms, in order, normalized to sum
+    /// to one; isoforms missing from the table get weight zero.
+    pub fn weights(&self, gene: &Gene) -> Result<Vec<f64>> {
+        let levels: Vec<f64> = gene
+            .isoforms
+            .iter()
+            .map(|isoform| self.levels.get(&isoform.name).copied().unwrap_or(0.0))
+            .collect();
+        let total: f64 = levels.iter().sum();
+        if total <= 0.0 {
+            return Err(MCL1Error::MissingParameter(format!("expression of {} isoforms", gene.symbol)));
+        }
+        Ok(levels.into_iter().map(|level| level / total).collect())
+    }
+}
@@ -1,7 +1,15 @@
-rust
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Metabolic pathways scored by `MCL1Analyzer` and the genes that mark them.
+pub const METABOLIC_GENE_SETS: &[(&str, &[&str])] = &[
+    ("mtorc1_signaling", &["MTOR", "RPTOR", "RHEB", "TSC1", "TSC2", "RPS6KB1", "EIF4EBP1"]),
+    ("oxidative_phosphorylation", &["ATP5F1A", "NDUFA4", "COX4I1", "SDHA", "UQCRC1"]),
+    ("fatty_acid_oxidation", &["CPT1A", "ACADM", "HADHA", "VLCAD"]),
+    ("mitochondrial_dynamics", &["DNM1L", "OPA1", "MFN1", "MFN2"]),
+    ("glycolysis", &["HK2", "PFKP", "PKM", "LDHA"]),
+];
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MetabolicPathway {
     pub id: String,
@@ -10,9 +18,12 @@ pub struct MetabolicPathway {
     pub genes_involved: Vec<String>,
     pub activation_state: PathwayActivation,
     pub mcl1_interaction_score: f64,
+    /// Proteins linking MCL1 to this pathway in the analyzed network.
+    #[serde(default)]
+    pub associated_proteins: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PathwayActivation {
     Active,
     Inactive,
@@ -34,6 +45,7 @@ impl MetabolicPathway {
             genes_involved,
             activation_state: PathwayActivation::Conditional,
             mcl1_interaction_score,
+            associated_proteins: Vec::new(),
         }
     }
 
@@ -50,7 +62,7 @@ impl MetabolicPathway {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PathwayCollection {
     pub pathways: HashMap<String, MetabolicPathway>,
 }
@@ -85,4 +97,4 @@ impl PathwayCollection {
     pub fn inactive_pathways(&self) -> impl Iterator<Item = &MetabolicPathway> {
         self.pathways.values().filter(|p| p.is_inactive())
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,13 @@
+pub mod drug_target;
+pub mod interaction;
+pub mod isoform;
+pub mod metabolic_pathway;
+pub mod protein;
+pub mod ptm;
+
+pub use drug_target::DrugTarget;
+pub use interaction::{Direction, InteractionEdge, Sign};
+pub use isoform::{ExpressionTable, Gene, Isoform, IsoformFunction};
+pub use metabolic_pathway::{MetabolicPathway, PathwayActivation, PathwayCollection};
+pub use protein::{Domain, Interaction, InteractionType, Protein};
+pub use ptm::{ModificationType, PtmSite};
@@ -1,6 +1,9 @@
-rust
 use serde::{Deserialize, Serialize};
 
+use crate::error::{MCL1Error, Result};
+use crate::models::interaction::{Direction, InteractionEdge, Sign};
+use crate::models::ptm::PtmSite;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Protein {
     pub id: String,
@@ -10,6 +13,8 @@ pub struct Protein {
     pub isoelectric_point: f64,
     pub domains: Vec<Domain>,
     pub interactions: Vec<Interaction>,
+    #[serde(default)]
+    pub ptm_sites: Vec<PtmSite>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +24,9 @@ pub struct Domain {
     pub start: usize,
     pub end: usize,
     pub description: String,
+    /// Score of the prediction that produced this domain, if any.
+    #[serde(default)]
+    pub score: Option<f64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,7 +38,7 @@ pub struct Interaction {
     pub evidence: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum InteractionType {
     Binding,
     Inhibition,
@@ -39,6 +47,74 @@ pub enum InteractionType {
     Unknown,
 }
 
+/// PSI-MI interaction type terms and the interaction type each maps to.
+const MI_INTERACTION_TYPES: &[(&str, InteractionType)] = &[
+    ("MI:0915", InteractionType::Binding),
+    ("MI:0407", InteractionType::Binding),
+    ("MI:0914", InteractionType::Binding),
+    ("MI:0195", InteractionType::Binding),
+    ("MI:0414", InteractionType::Modification),
+    ("MI:0217", InteractionType::Modification),
+    ("MI:0203", InteractionType::Modification),
+    ("MI:0220", InteractionType::Modification),
+    ("MI:0570", InteractionType::Modification),
+    ("MI:2235", InteractionType::Activation),
+    ("MI:2236", InteractionType::Activation),
+    ("MI:2237", InteractionType::Activation),
+    ("MI:2240", InteractionType::Inhibition),
+    ("MI:2241", InteractionType::Inhibition),
+    ("MI:2242", InteractionType::Inhibition),
+];
+
+/// Maps a PSI-MI term such as `psi-mi:"MI:0915"(physical association)`.
+pub fn interaction_type_from_mi(term: &str) -> Option<InteractionType> {
+    MI_INTERACTION_TYPES
+        .iter()
+        .find(|(id, _)| term.contains(id))
+        .map(|(_, kind)| kind.clone())
+}
+
+impl InteractionType {
+    /// Parses a free-form interaction label, falling back to `Unknown`.
+    pub fn from_label(label: &str) -> Self {
+        if label.contains("MI:") {
+            return interaction_type_from_mi(label).unwrap_or(InteractionType::Unknown);
+        }
+        match label.trim().to_ascii_lowercase().as_str() {
+            "binding" | "physical" | "complex" | "association" => InteractionType::Binding,
+            "inhibition" | "inhibits" | "repression" | "inactivation" => InteractionType::Inhibition,
+            "activation" | "activates" | "stimulation" => InteractionType::Activation,
+            "modification" | "phosphorylation" | "ubiquitination" | "ptmod" => InteractionType::Modification,
+            _ => InteractionType::Unknown,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            InteractionType::Binding => "binding",
+            InteractionType::Inhibition => "inhibition",
+            InteractionType::Activation => "activation",
+            InteractionType::Modification => "modification",
+            InteractionType::Unknown => "unknown",
+        }
+    }
+
+    pub fn default_direction(&self) -> Direction {
+        match self {
+            InteractionType::Binding | InteractionType::Unknown => Direction::Undirected,
+            _ => Direction::Directed,
+        }
+    }
+
+    pub fn default_sign(&self) -> Sign {
+        match self {
+            InteractionType::Activation => Sign::Positive,
+            InteractionType::Inhibition => Sign::Negative,
+            _ => Sign::Unsigned,
+        }
+    }
+}
+
 impl Protein {
     pub fn new(
         id: String,
@@ -55,6 +131,7 @@ impl Protein {
             isoelectric_point,
             domains: Vec::new(),
             interactions: Vec::new(),
+            ptm_sites: Vec::new(),
         }
     }
 
@@ -62,6 +139,63 @@ impl Protein {
         self.domains.push(domain);
     }
 
+    /// Records a modification site, checking its residue against the
+    /// sequence when one is present. A site already recorded at the same
+    /// position with the same modification is replaced.
+    pub fn add_ptm_site(&mut self, site: PtmSite) -> Result<()> {
+        if !self.sequence.is_empty() {
+            let actual = self.sequence.chars().nth(site.position.wrapping_sub(1));
+            if actual.map(|r| r.to_ascii_uppercase()) != Some(site.residue) {
+                return Err(MCL1Error::InvalidSite {
+                    protein: self.id.clone(),
+                    site: site.label(),
+                    message: match actual {
+                        Some(residue) => format!("sequence has '{}' at {}", residue, site.position),
+                        None => format!("position is outside the {}-residue sequence", self.sequence.len()),
+                    },
+                });
+            }
+        }
+        self.ptm_sites
+            .retain(|s| !(s.position == site.position && s.modification == site.modification));
+        self.ptm_sites.push(site);
+        self.ptm_sites.sort_by_key(|s| s.position);
+        Ok(())
+    }
+
+    pub fn ptm_site(&self, position: usize) -> Option<&PtmSite> {
+        self.ptm_sites.iter().find(|site| site.position == position)
+    }
+
+    /// Sets the modification state of the site at `position`.
+    pub fn set_site_modified(&mut self, position: usize, modified: bool) -> Result<()> {
+        let id = self.id.clone();
+        let site = self
+            .ptm_sites
+            .iter_mut()
+            .find(|site| site.position == position)
+            .ok_or_else(|| MCL1Error::InvalidSite {
+                protein: id,
+                site: position.to_string(),
+                message: "no modification site recorded".to_string(),
+            })?;
+        site.modified = modified;
+        Ok(())
+    }
+
+    /// Sets every site written by `enzyme`, e.g. to model a kinase
+    /// inhibitor leaving its sites unphosphorylated. Returns the site count.
+    pub fn set_enzyme_sites_modified(&mut self, enzyme: &str, modified: bool) -> usize {
+        let mut count = 0;
+        for site in &mut self.ptm_sites {
+            if site.enzyme.as_deref().is_some_and(|e| e.eq_ignore_ascii_case(enzyme)) {
+                site.modified = modified;
+                count += 1;
+            }
+        }
+        count
+    }
+
     pub fn add_interaction(&mut self, interaction: Interaction) {
         self.interactions.push(interaction);
     }
@@ -88,4 +222,30 @@ impl Protein {
     pub fn get_activators(&self) -> Vec<&Interaction> {
         self.get_interactions_by_type(&InteractionType::Activation)
     }
-}
\ No newline at end of file
+
+    /// Records every edge leaving this protein, plus undirected edges that
+    /// reach it, as interactions on the protein.
+    pub fn add_edges<'a, I>(&mut self, edges: I)
+    where
+        I: IntoIterator<Item = &'a InteractionEdge>,
+    {
+        for edge in edges {
+            if edge.source == self.id {
+                self.interactions.push(edge.to_protein_interaction());
+            } else if edge.target == self.id && !edge.is_directed() {
+                let mut reversed = edge.clone();
+                reversed.source = edge.target.clone();
+                reversed.target = edge.source.clone();
+                reversed.target_name = None;
+                self.interactions.push(reversed.to_protein_interaction());
+            }
+        }
+    }
+
+    pub fn edges(&self) -> Vec<InteractionEdge> {
+        self.interactions
+            .iter()
+            .map(|interaction| InteractionEdge::from_protein_interaction(&self.id, interaction))
+            .collect()
+    }
+}
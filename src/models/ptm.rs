@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+
+/// Kind of post-translational modification at a site.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ModificationType {
+    Phosphorylation,
+    Ubiquitination,
+    Acetylation,
+    Methylation,
+    Sumoylation,
+    Other,
+}
+
+impl ModificationType {
+    /// Maps UniProt modification notes ("Phosphoserine", "N6-acetyllysine",
+    /// "Glycyl lysine isopeptide ... ubiquitin") and plain labels.
+    pub fn from_label(label: &str) -> Self {
+        let label = label.to_lowercase();
+        if label.contains("phospho") {
+            ModificationType::Phosphorylation
+        } else if label.contains("ubiquitin") {
+            ModificationType::Ubiquitination
+        } else if label.contains("sumo") {
+            ModificationType::Sumoylation
+        } else if label.contains("acetyl") {
+            ModificationType::Acetylation
+        } else if label.contains("methyl") {
+            ModificationType::Methylation
+        } else {
+            ModificationType::Other
+        }
+    }
+}
+
+/// A modifiable residue and whether it is currently modified.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PtmSite {
+    /// One-letter residue code, e.g. `'S'`.
+    pub residue: char,
+    /// 1-based position in the protein sequence.
+    pub position: usize,
+    pub modification: ModificationType,
+    /// Writer enzyme (kinase, E3 ligase, ...) if known.
+    pub enzyme: Option<String>,
+    pub modified: bool,
+}
+
+impl PtmSite {
+    pub fn new(residue: char, position: usize, modification: ModificationType) -> Self {
+        Self {
+            residue: residue.to_ascii_uppercase(),
+            position,
+            modification,
+            enzyme: None,
+            modified: false,
+        }
+    }
+
+    pub fn with_enzyme(mut self, enzyme: &str) -> Self {
+        self.enzyme = Some(enzyme.to_string());
+        self
+    }
+
+    pub fn modified(mut self) -> Self {
+        self.modified = true;
+        self
+    }
+
+    /// Site label such as `S159`.
+    pub fn label(&self) -> String {
+        format!("{}{}", self.residue, self.position)
+    }
+}
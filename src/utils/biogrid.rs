@@ -0,0 +1,151 @@
+//! Loader for BioGRID TAB3 interaction files
+//!
+//! BioGRID carries no numeric score, so edge confidence is assigned from the
+//! throughput of the supporting experiment.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use crate::analysis::interaction_network::{InteractionNetwork, ProteinInteraction};
+use crate::error::{MCL1Error, Result};
+
+const ENTREZ_A: usize = 1;
+const ENTREZ_B: usize = 2;
+const SYMBOL_A: usize = 7;
+const SYMBOL_B: usize = 8;
+const EXPERIMENTAL_SYSTEM_TYPE: usize = 12;
+const ORGANISM_A: usize = 15;
+const ORGANISM_B: usize = 16;
+const THROUGHPUT: usize = 17;
+const MODIFICATION: usize = 19;
+/// Columns up to and including `Modification` must be present.
+const MIN_COLUMNS: usize = MODIFICATION + 1;
+
+/// Builds an `InteractionNetwork` from a BioGRID TAB3 file.
+#[derive(Debug, Clone)]
+pub struct BiogridLoader {
+    pub low_throughput_confidence: f64,
+    pub high_throughput_confidence: f64,
+    /// Keep only interactions where both interactors belong to this taxon.
+    pub taxon_id: Option<u32>,
+    /// Skip genetic interactions.
+    pub physical_only: bool,
+}
+
+impl Default for BiogridLoader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BiogridLoader {
+    pub fn new() -> Self {
+        Self {
+            low_throughput_confidence: 0.9,
+            high_throughput_confidence: 0.6,
+            taxon_id: None,
+            physical_only: false,
+        }
+    }
+
+    pub fn with_taxon(mut self, taxon_id: u32) -> Self {
+        self.taxon_id = Some(taxon_id);
+        self
+    }
+
+    pub fn with_throughput_confidence(mut self, low: f64, high: f64) -> Self {
+        self.low_throughput_confidence = low;
+        self.high_throughput_confidence = high;
+        self
+    }
+
+    pub fn physical_only(mut self) -> Self {
+        self.physical_only = true;
+        self
+    }
+
+    /// Confidence for a `Throughput` field; when both throughputs are
+    /// listed the stronger evidence wins.
+    pub fn throughput_confidence(&self, throughput: &str) -> Option<f64> {
+        let throughput = throughput.to_ascii_lowercase();
+        if throughput.contains("low throughput") {
+            Some(self.low_throughput_confidence)
+        } else if throughput.contains("high throughput") {
+            Some(self.high_throughput_confidence)
+        } else {
+            None
+        }
+    }
+
+    pub fn load<P: AsRef<Path>>(&self, path: P) -> Result<InteractionNetwork> {
+        let file = path.as_ref().display().to_string();
+        self.load_from(BufReader::new(File::open(path)?), &file)
+    }
+
+    pub fn load_from<R: BufRead>(&self, reader: R, file: &str) -> Result<InteractionNetwork> {
+        MCL1Error::check_range("low_throughput_confidence", self.low_throughput_confidence, 0.0, 1.0)?;
+        MCL1Error::check_range("high_throughput_confidence", self.high_throughput_confidence, 0.0, 1.0)?;
+
+        let mut network = InteractionNetwork::new();
+
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line_number = index + 1;
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() < MIN_COLUMNS {
+                return Err(MCL1Error::parse(
+                    file,
+                    line_number,
+                    fields.len(),
+                    format!("expected at least {} columns, found {}", MIN_COLUMNS, fields.len()),
+                ));
+            }
+
+            let organism = |column: usize| -> Result<u32> {
+                fields[column].parse().map_err(|e| {
+                    MCL1Error::parse(file, line_number, column + 1, format!("invalid organism '{}': {}", fields[column], e))
+                })
+            };
+            let (organism_a, organism_b) = (organism(ORGANISM_A)?, organism(ORGANISM_B)?);
+            if let Some(taxon) = self.taxon_id {
+                if organism_a != taxon || organism_b != taxon {
+                    continue;
+                }
+            }
+
+            let physical = fields[EXPERIMENTAL_SYSTEM_TYPE].eq_ignore_ascii_case("physical");
+            if self.physical_only && !physical {
+                continue;
+            }
+
+            let confidence = self.throughput_confidence(fields[THROUGHPUT]).ok_or_else(|| {
+                MCL1Error::parse(
+                    file,
+                    line_number,
+                    THROUGHPUT + 1,
+                    format!("unknown throughput '{}'", fields[THROUGHPUT]),
+                )
+            })?;
+
+            let interaction_type = match fields[MODIFICATION] {
+                "-" | "" if physical => "binding".to_string(),
+                "-" | "" => "genetic".to_string(),
+                modification => modification.to_ascii_lowercase(),
+            };
+
+            let (symbol_a, symbol_b) = (fields[SYMBOL_A], fields[SYMBOL_B]);
+            network.add_node_attribute(symbol_a, "entrez_id", fields[ENTREZ_A]);
+            network.add_node_attribute(symbol_b, "entrez_id", fields[ENTREZ_B]);
+            network.add_node_attribute(symbol_a, "taxid", &organism_a.to_string());
+            network.add_node_attribute(symbol_b, "taxid", &organism_b.to_string());
+
+            network.add_interaction(ProteinInteraction::new(symbol_a, symbol_b, &interaction_type, confidence));
+        }
+
+        Ok(network)
+    }
+}
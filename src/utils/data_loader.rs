@@ -1,8 +1,18 @@
+use std::collections::HashMap;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, Read};
 use std::path::Path;
 
-pub fn load_csv_data<P>(path: P) -> Result<Vec<Vec<String>>, Box<dyn std::error::Error>>
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::interaction_network::ProteinInteraction;
+use crate::analysis::variant_effect::VariantRecord;
+use crate::error::{MCL1Error, Result};
+use crate::models::isoform::ExpressionRecord;
+use crate::models::{DrugTarget, MetabolicPathway};
+
+pub fn load_csv_data<P>(path: P) -> Result<Vec<Vec<String>>>
 where
     P: AsRef<Path>,
 {
@@ -20,4 +30,212 @@ where
     }
 
     Ok(data)
-}
\ No newline at end of file
+}
+
+/// Header-driven loader that deserializes delimited rows into typed records.
+///
+/// Columns are matched by header name; `map_column` renames a file header to
+/// the field it should fill. Rows that fail to parse or validate are
+/// collected in `TableLoad::row_errors` instead of aborting the load.
+#[derive(Debug, Clone)]
+pub struct TableLoader {
+    pub delimiter: u8,
+    pub column_map: HashMap<String, String>,
+}
+
+/// Records and per-row errors from one `TableLoader` run.
+#[derive(Debug)]
+pub struct TableLoad<T> {
+    pub records: Vec<T>,
+    pub row_errors: Vec<MCL1Error>,
+}
+
+impl<T> TableLoad<T> {
+    pub fn is_clean(&self) -> bool {
+        self.row_errors.is_empty()
+    }
+}
+
+/// Pathway row as stored on disk; `genes` is a `;`-separated list.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PathwayRecord {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub genes: String,
+    pub mcl1_interaction_score: f64,
+}
+
+impl PathwayRecord {
+    pub fn into_pathway(self) -> MetabolicPathway {
+        let genes = self
+            .genes
+            .split(';')
+            .map(|g| g.trim().to_string())
+            .filter(|g| !g.is_empty())
+            .collect();
+        MetabolicPathway::new(self.id, self.name, self.description, genes, self.mcl1_interaction_score)
+    }
+}
+
+impl Default for TableLoader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TableLoader {
+    pub fn new() -> Self {
+        Self {
+            delimiter: b',',
+            column_map: HashMap::new(),
+        }
+    }
+
+    pub fn tsv() -> Self {
+        Self::new().with_delimiter(b'\t')
+    }
+
+    pub fn with_delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Reads the file column `header` into the record field `field`.
+    pub fn map_column(mut self, header: &str, field: &str) -> Self {
+        self.column_map.insert(header.to_string(), field.to_string());
+        self
+    }
+
+    pub fn load<T, P>(&self, path: P) -> Result<TableLoad<T>>
+    where
+        T: DeserializeOwned,
+        P: AsRef<Path>,
+    {
+        let file = path.as_ref().display().to_string();
+        self.load_reader(File::open(path)?, &file)
+    }
+
+    /// Like `load`, but reads from any reader; `file` labels row errors.
+    pub fn load_reader<T, R>(&self, reader: R, file: &str) -> Result<TableLoad<T>>
+    where
+        T: DeserializeOwned,
+        R: Read,
+    {
+        self.load_validated(reader, file, |_: &T| Ok(()))
+    }
+
+    pub fn load_interactions<P: AsRef<Path>>(&self, path: P) -> Result<TableLoad<ProteinInteraction>> {
+        let file = path.as_ref().display().to_string();
+        self.load_validated(File::open(path)?, &file, |interaction: &ProteinInteraction| {
+            MCL1Error::check_range("confidence", interaction.confidence, 0.0, 1.0).map(|_| ())
+        })
+    }
+
+    pub fn load_drug_targets<P: AsRef<Path>>(&self, path: P) -> Result<TableLoad<DrugTarget>> {
+        let file = path.as_ref().display().to_string();
+        self.load_validated(File::open(path)?, &file, |target: &DrugTarget| {
+            MCL1Error::check_range("cardiotoxicity_risk", target.cardiotoxicity_risk as f64, 0.0, 1.0)?;
+            MCL1Error::check_range("selectivity_score", target.selectivity_score as f64, 0.0, 1.0)?;
+            Ok(())
+        })
+    }
+
+    pub fn load_expression<P: AsRef<Path>>(&self, path: P) -> Result<TableLoad<ExpressionRecord>> {
+        let file = path.as_ref().display().to_string();
+        self.load_validated(File::open(path)?, &file, |record: &ExpressionRecord| {
+            MCL1Error::check_range("expression", record.expression, 0.0, f64::INFINITY).map(|_| ())
+        })
+    }
+
+    /// Loads `gene,hgvs` rows; rows whose HGVS notation does not parse are
+    /// reported as row errors.
+    pub fn load_variants<P: AsRef<Path>>(&self, path: P) -> Result<TableLoad<VariantRecord>> {
+        let file = path.as_ref().display().to_string();
+        self.load_validated(File::open(path)?, &file, |record: &VariantRecord| record.variant().map(|_| ()))
+    }
+
+    pub fn load_pathways<P: AsRef<Path>>(&self, path: P) -> Result<TableLoad<MetabolicPathway>> {
+        let load = self.load::<PathwayRecord, _>(path)?;
+        Ok(TableLoad {
+            records: load.records.into_iter().map(PathwayRecord::into_pathway).collect(),
+            row_errors: load.row_errors,
+        })
+    }
+
+    /// Deserializes every row and runs `check` on it; `InvalidScore` errors
+    /// from `check` are reported at the column of the offending field.
+    fn load_validated<T, R, F>(&self, reader: R, file: &str, check: F) -> Result<TableLoad<T>>
+    where
+        T: DeserializeOwned,
+        R: Read,
+        F: Fn(&T) -> Result<()>,
+    {
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(self.delimiter)
+            .trim(csv::Trim::All)
+            .from_reader(reader);
+
+        let headers = reader
+            .headers()
+            .map_err(|e| Self::convert_error(file, e))?
+            .iter()
+            .map(|h| self.column_map.get(h).map(String::as_str).unwrap_or(h))
+            .collect::<csv::StringRecord>();
+
+        let mut load = TableLoad {
+            records: Vec::new(),
+            row_errors: Vec::new(),
+        };
+        for row in reader.records() {
+            let row = match row {
+                Ok(row) => row,
+                Err(e) if e.is_io_error() => return Err(Self::convert_error(file, e)),
+                Err(e) => {
+                    load.row_errors.push(Self::convert_error(file, e));
+                    continue;
+                }
+            };
+            let line = row.position().map(|p| p.line() as usize).unwrap_or(0);
+
+            let record: T = match row.deserialize(Some(&headers)) {
+                Ok(record) => record,
+                Err(e) => {
+                    let column = match e.kind() {
+                        csv::ErrorKind::Deserialize { err, .. } => err.field().map(|f| f as usize + 1).unwrap_or(0),
+                        _ => 0,
+                    };
+                    load.row_errors.push(MCL1Error::parse(file, line, column, e.to_string()));
+                    continue;
+                }
+            };
+
+            match check(&record) {
+                Ok(()) => load.records.push(record),
+                Err(e) => {
+                    let column = match &e {
+                        MCL1Error::InvalidScore { name, .. } => {
+                            headers.iter().position(|h| h == name).map(|i| i + 1).unwrap_or(0)
+                        }
+                        _ => 0,
+                    };
+                    load.row_errors.push(MCL1Error::parse(file, line, column, e.to_string()));
+                }
+            }
+        }
+
+        Ok(load)
+    }
+
+    fn convert_error(file: &str, error: csv::Error) -> MCL1Error {
+        let line = error.position().map(|p| p.line() as usize).unwrap_or(0);
+        let message = error.to_string();
+        match error.into_kind() {
+            csv::ErrorKind::Io(e) => MCL1Error::Io(e),
+            csv::ErrorKind::Utf8 { err, .. } => MCL1Error::parse(file, line, err.field() + 1, message),
+            _ => MCL1Error::parse(file, line, 0, message),
+        }
+    }
+}
@@ -0,0 +1,224 @@
+//! Streaming multi-FASTA reader and writer for protein sequences
+//!
+//! Gzip-compressed input is detected from its magic bytes, so `.fa` and
+//! `.fa.gz` files load through the same call.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, Lines, Write};
+use std::path::Path;
+
+use flate2::read::MultiGzDecoder;
+
+use crate::error::{MCL1Error, Result};
+use crate::models::protein::Protein;
+
+/// One-letter codes accepted in sequences: the 20 standard residues plus
+/// selenocysteine, pyrrolysine and the ambiguity codes B, Z, J and X.
+pub const AMINO_ACID_ALPHABET: &str = "ACDEFGHIKLMNPQRSTVWYUOBZJX";
+
+/// Default residues per line when writing.
+pub const DEFAULT_LINE_WIDTH: usize = 60;
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Iterator over the `Protein` records of a FASTA stream.
+pub struct FastaReader<R: BufRead> {
+    lines: Lines<R>,
+    file: String,
+    line_number: usize,
+    pending_header: Option<String>,
+    validate: bool,
+}
+
+/// Opens a FASTA file, decompressing it if it is gzip-compressed.
+pub fn open_fasta<P: AsRef<Path>>(path: P) -> Result<FastaReader<Box<dyn BufRead>>> {
+    let file = path.as_ref().display().to_string();
+    let mut reader = BufReader::new(File::open(path)?);
+    let is_gzip = reader.fill_buf()?.starts_with(&GZIP_MAGIC);
+
+    let reader: Box<dyn BufRead> = if is_gzip {
+        Box::new(BufReader::new(MultiGzDecoder::new(reader)))
+    } else {
+        Box::new(reader)
+    };
+    Ok(FastaReader::new(reader, &file))
+}
+
+/// Reads every record of a FASTA file.
+pub fn read_fasta<P: AsRef<Path>>(path: P) -> Result<Vec<Protein>> {
+    open_fasta(path)?.collect()
+}
+
+/// Splits a header into protein ID and name.
+///
+/// UniProt headers (`sp|Q07820|MCL1_HUMAN ... GN=MCL1`) give the accession
+/// and gene name; other headers use their first word for both.
+pub fn parse_header(header: &str) -> (String, String) {
+    let identifier = header.split_whitespace().next().unwrap_or("");
+    let gene_name = header
+        .split_whitespace()
+        .find_map(|word| word.strip_prefix("GN="))
+        .map(str::to_string);
+
+    let parts: Vec<&str> = identifier.split('|').collect();
+    match parts.as_slice() {
+        ["sp" | "tr", accession, entry_name, ..] => (
+            accession.to_string(),
+            gene_name.unwrap_or_else(|| entry_name.to_string()),
+        ),
+        _ => (
+            identifier.to_string(),
+            gene_name.unwrap_or_else(|| identifier.to_string()),
+        ),
+    }
+}
+
+impl<R: BufRead> FastaReader<R> {
+    pub fn new(reader: R, file: &str) -> Self {
+        Self {
+            lines: reader.lines(),
+            file: file.to_string(),
+            line_number: 0,
+            pending_header: None,
+            validate: true,
+        }
+    }
+
+    /// Accept any characters in sequence lines.
+    pub fn without_validation(mut self) -> Self {
+        self.validate = false;
+        self
+    }
+
+    fn next_line(&mut self) -> Option<Result<String>> {
+        let line = self.lines.next()?;
+        self.line_number += 1;
+        Some(line.map_err(MCL1Error::from))
+    }
+
+    fn check_residues(&self, line: &str) -> Result<()> {
+        if !self.validate {
+            return Ok(());
+        }
+        let trimmed = line.trim_end_matches('*');
+        match trimmed
+            .char_indices()
+            .find(|(_, c)| !AMINO_ACID_ALPHABET.contains(c.to_ascii_uppercase()))
+        {
+            Some((column, residue)) => Err(MCL1Error::parse(
+                &self.file,
+                self.line_number,
+                column + 1,
+                format!("invalid amino acid '{}'", residue),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    fn read_record(&mut self) -> Option<Result<Protein>> {
+        let header = match self.pending_header.take() {
+            Some(header) => header,
+            None => loop {
+                match self.next_line()? {
+                    Ok(line) if line.trim().is_empty() || line.starts_with(';') => continue,
+                    Ok(line) => match line.strip_prefix('>') {
+                        Some(header) => break header.to_string(),
+                        None => {
+                            return Some(Err(MCL1Error::parse(
+                                &self.file,
+                                self.line_number,
+                                1,
+                                "sequence data before the first '>' header",
+                            )))
+                        }
+                    },
+                    Err(e) => return Some(Err(e)),
+                }
+            },
+        };
+
+        let mut sequence = String::new();
+        while let Some(line) = self.next_line() {
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => return Some(Err(e)),
+            };
+            if let Some(next_header) = line.strip_prefix('>') {
+                self.pending_header = Some(next_header.to_string());
+                break;
+            }
+            let line = line.trim();
+            if let Err(e) = self.check_residues(line) {
+                return Some(Err(e));
+            }
+            sequence.extend(line.trim_end_matches('*').chars().map(|c| c.to_ascii_uppercase()));
+        }
+
+        let (id, name) = parse_header(&header);
+        Some(Ok(Protein::from_sequence(id, name, sequence)))
+    }
+}
+
+impl<R: BufRead> Iterator for FastaReader<R> {
+    type Item = Result<Protein>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_record()
+    }
+}
+
+/// Writes proteins as FASTA with a fixed number of residues per line.
+#[derive(Debug, Clone)]
+pub struct FastaWriter {
+    /// Residues per line; `0` writes each sequence on one line.
+    pub line_width: usize,
+}
+
+impl Default for FastaWriter {
+    fn default() -> Self {
+        Self::new(DEFAULT_LINE_WIDTH)
+    }
+}
+
+impl FastaWriter {
+    pub fn new(line_width: usize) -> Self {
+        Self { line_width }
+    }
+
+    pub fn write_protein<W: Write>(&self, protein: &Protein, writer: &mut W) -> Result<()> {
+        if protein.name.is_empty() || protein.name == protein.id {
+            writeln!(writer, ">{}", protein.id)?;
+        } else {
+            writeln!(writer, ">{} GN={}", protein.id, protein.name)?;
+        }
+
+        let residues: Vec<char> = protein.sequence.chars().collect();
+        let width = if self.line_width == 0 { residues.len().max(1) } else { self.line_width };
+        for chunk in residues.chunks(width) {
+            writeln!(writer, "{}", chunk.iter().collect::<String>())?;
+        }
+        Ok(())
+    }
+
+    pub fn write_all<'a, W, I>(&self, proteins: I, mut writer: W) -> Result<()>
+    where
+        W: Write,
+        I: IntoIterator<Item = &'a Protein>,
+    {
+        for protein in proteins {
+            self.write_protein(protein, &mut writer)?;
+        }
+        Ok(())
+    }
+
+    pub fn write_file<'a, P, I>(&self, proteins: I, path: P) -> Result<()>
+    where
+        P: AsRef<Path>,
+        I: IntoIterator<Item = &'a Protein>,
+    {
+        let mut writer = std::io::BufWriter::new(File::create(path)?);
+        self.write_all(proteins, &mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,27 @@
+use crate::analysis::interaction_network::ProteinInteraction;
+
+/// Proteins whose loss is known to compromise cardiomyocyte mitochondria.
+const CARDIAC_SENSITIVE_PROTEINS: &[&str] = &[
+    "MCL1", "DNM1L", "OPA1", "MFN1", "MFN2", "PPARGC1A", "BAK1", "BAX",
+];
+
+/// Weight applied to interactions that touch no cardiac-sensitive protein.
+const NON_CARDIAC_WEIGHT: f64 = 0.5;
+
+pub fn is_cardiac_sensitive(protein: &str) -> bool {
+    CARDIAC_SENSITIVE_PROTEINS
+        .iter()
+        .any(|p| p.eq_ignore_ascii_case(protein))
+}
+
+/// Scores how likely targeting the interaction's target is to harm cardiac
+/// tissue, scaled by the confidence of the interaction itself.
+pub fn calculate_cardiotoxicity_score(interaction: &ProteinInteraction) -> f64 {
+    let weight = if is_cardiac_sensitive(&interaction.target) {
+        1.0
+    } else {
+        NON_CARDIAC_WEIGHT
+    };
+
+    (interaction.confidence * weight).clamp(0.0, 1.0)
+}
@@ -0,0 +1,425 @@
+//! PSI-MI TAB (MITAB 2.5/2.6/2.7) import and export
+//!
+//! Records keep every column verbatim so that a network read from MITAB can
+//! be written back without losing detection methods or publications.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+
+use crate::analysis::interaction_network::{InteractionNetwork, ProteinInteraction};
+use crate::error::{MCL1Error, Result};
+use crate::models::protein::{interaction_type_from_mi, InteractionType};
+
+/// Number of columns in MITAB 2.5; later versions append columns.
+pub const MITAB25_COLUMNS: usize = 15;
+/// Number of columns in MITAB 2.7, the widest version written.
+pub const MITAB27_COLUMNS: usize = 42;
+
+/// Confidence used when a record carries no parseable score.
+pub const DEFAULT_CONFIDENCE: f64 = 0.5;
+
+/// Header names of every MITAB 2.7 column; 2.5 and 2.6 use a prefix.
+const MITAB_COLUMN_NAMES: [&str; MITAB27_COLUMNS] = [
+    "ID(s) interactor A",
+    "ID(s) interactor B",
+    "Alt. ID(s) interactor A",
+    "Alt. ID(s) interactor B",
+    "Alias(es) interactor A",
+    "Alias(es) interactor B",
+    "Interaction detection method(s)",
+    "Publication 1st author(s)",
+    "Publication Identifier(s)",
+    "Taxid interactor A",
+    "Taxid interactor B",
+    "Interaction type(s)",
+    "Source database(s)",
+    "Interaction identifier(s)",
+    "Confidence value(s)",
+    "Expansion method(s)",
+    "Biological role(s) interactor A",
+    "Biological role(s) interactor B",
+    "Experimental role(s) interactor A",
+    "Experimental role(s) interactor B",
+    "Type(s) interactor A",
+    "Type(s) interactor B",
+    "Xref(s) interactor A",
+    "Xref(s) interactor B",
+    "Interaction Xref(s)",
+    "Annotation(s) interactor A",
+    "Annotation(s) interactor B",
+    "Interaction annotation(s)",
+    "Host organism(s)",
+    "Interaction parameter(s)",
+    "Creation date",
+    "Update date",
+    "Checksum(s) interactor A",
+    "Checksum(s) interactor B",
+    "Interaction Checksum(s)",
+    "Negative",
+    "Feature(s) interactor A",
+    "Feature(s) interactor B",
+    "Stoichiometry(s) interactor A",
+    "Stoichiometry(s) interactor B",
+    "Identification method participant A",
+    "Identification method participant B",
+];
+
+/// One MITAB line with every column kept as written.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MitabRecord {
+    pub columns: Vec<String>,
+}
+
+/// Network built from a MITAB file together with the records behind it.
+/// Rows that are too short or carry a score outside `[0, 1]` are skipped
+/// and reported in `row_errors`.
+#[derive(Debug)]
+pub struct MitabImport {
+    pub network: InteractionNetwork,
+    pub records: Vec<MitabRecord>,
+    pub row_errors: Vec<MCL1Error>,
+}
+
+/// Reads MITAB files into an `InteractionNetwork`.
+#[derive(Debug, Clone, Default)]
+pub struct MitabReader {
+    /// Use the `(gene name)` alias as node ID instead of the accession.
+    pub prefer_gene_names: bool,
+}
+
+/// PSI-MI term written for an interaction type.
+pub fn mi_term_for(kind: &InteractionType) -> &'static str {
+    match kind {
+        InteractionType::Binding => "psi-mi:\"MI:0915\"(physical association)",
+        InteractionType::Modification => "psi-mi:\"MI:0414\"(enzymatic reaction)",
+        InteractionType::Activation => "psi-mi:\"MI:2235\"(up-regulates)",
+        InteractionType::Inhibition => "psi-mi:\"MI:2240\"(down-regulates)",
+        InteractionType::Unknown => "psi-mi:\"MI:0190\"(interaction type)",
+    }
+}
+
+/// Splits a MITAB field into its `|`-separated entries, treating `-` as empty.
+fn entries(field: &str) -> impl Iterator<Item = &str> {
+    field.split('|').map(str::trim).filter(|e| !e.is_empty() && *e != "-")
+}
+
+/// Splits `db:value(description)` into its parts.
+fn split_xref(entry: &str) -> (&str, &str, Option<&str>) {
+    let (db, rest) = entry.split_once(':').unwrap_or(("", entry));
+    let (value, description) = match rest.find('(') {
+        Some(open) if rest.ends_with(')') => (&rest[..open], Some(&rest[open + 1..rest.len() - 1])),
+        _ => (rest, None),
+    };
+    (db, value.trim_matches('"'), description)
+}
+
+fn dash_if_empty(value: String) -> String {
+    if value.is_empty() {
+        "-".to_string()
+    } else {
+        value
+    }
+}
+
+impl MitabRecord {
+    pub fn parse(line: &str, file: &str, line_number: usize) -> Result<Self> {
+        let columns: Vec<String> = line.split('\t').map(str::to_string).collect();
+        if columns.len() < MITAB25_COLUMNS {
+            return Err(MCL1Error::parse(
+                file,
+                line_number,
+                columns.len(),
+                format!("expected at least {} columns, found {}", MITAB25_COLUMNS, columns.len()),
+            ));
+        }
+        Ok(Self { columns })
+    }
+
+    fn column(&self, index: usize) -> &str {
+        self.columns.get(index).map(String::as_str).unwrap_or("-")
+    }
+
+    /// `(namespace, accession)` of the first identifier of interactor A or B.
+    pub fn primary_id(&self, interactor_b: bool) -> (&str, &str) {
+        let field = self.column(if interactor_b { 1 } else { 0 });
+        entries(field)
+            .next()
+            .map(|entry| {
+                let (db, value, _) = split_xref(entry);
+                (db, value)
+            })
+            .unwrap_or(("", ""))
+    }
+
+    pub fn gene_name(&self, interactor_b: bool) -> Option<&str> {
+        let field = self.column(if interactor_b { 5 } else { 4 });
+        entries(field).find_map(|entry| match split_xref(entry) {
+            (_, value, Some("gene name")) => Some(value),
+            _ => None,
+        })
+    }
+
+    pub fn taxid(&self, interactor_b: bool) -> Option<&str> {
+        let field = self.column(if interactor_b { 10 } else { 9 });
+        entries(field).next().map(|entry| split_xref(entry).1)
+    }
+
+    pub fn detection_methods(&self) -> Vec<&str> {
+        entries(self.column(6)).collect()
+    }
+
+    pub fn publications(&self) -> Vec<&str> {
+        entries(self.column(8)).collect()
+    }
+
+    pub fn interaction_type_terms(&self) -> Vec<&str> {
+        entries(self.column(11)).collect()
+    }
+
+    pub fn interaction_type(&self) -> InteractionType {
+        self.interaction_type_terms()
+            .iter()
+            .find_map(|term| interaction_type_from_mi(term))
+            .unwrap_or(InteractionType::Unknown)
+    }
+
+    /// Index among the confidence entries of the MI-score if present,
+    /// otherwise of the first numeric value.
+    fn score_entry(values: &[(&str, &str)]) -> Option<usize> {
+        values
+            .iter()
+            .position(|(kind, _)| kind.ends_with("miscore"))
+            .or_else(|| values.iter().position(|(_, value)| value.parse::<f64>().is_ok()))
+    }
+
+    /// The MI-score if present, otherwise the first numeric confidence value.
+    pub fn confidence(&self) -> Option<f64> {
+        let values: Vec<(&str, &str)> = entries(self.column(14))
+            .map(|entry| entry.split_once(':').unwrap_or(("", entry)))
+            .collect();
+        Self::score_entry(&values).and_then(|i| values[i].1.parse().ok())
+    }
+
+    /// Copy whose score, the value `confidence` reads, is replaced; other
+    /// confidence entries are kept.
+    pub fn with_confidence(&self, confidence: f64) -> Self {
+        let values: Vec<(&str, &str)> = entries(self.column(14))
+            .map(|entry| entry.split_once(':').unwrap_or(("", entry)))
+            .collect();
+        let mut scores: Vec<String> = entries(self.column(14)).map(str::to_string).collect();
+        match Self::score_entry(&values) {
+            Some(i) if values[i].0.is_empty() => scores[i] = confidence.to_string(),
+            Some(i) => scores[i] = format!("{}:{}", values[i].0, confidence),
+            None => scores.insert(0, format!("intact-miscore:{}", confidence)),
+        }
+
+        let mut record = self.clone();
+        if record.columns.len() < MITAB25_COLUMNS {
+            record.columns.resize(MITAB25_COLUMNS, "-".to_string());
+        }
+        record.columns[14] = scores.join("|");
+        record
+    }
+
+    /// Node ID for interactor A or B under the reader's naming choice.
+    fn node_id(&self, interactor_b: bool, prefer_gene_names: bool) -> String {
+        let gene = if prefer_gene_names { self.gene_name(interactor_b) } else { None };
+        gene.unwrap_or_else(|| self.primary_id(interactor_b).1).to_string()
+    }
+
+    /// Builds a MITAB 2.5 record from a network edge and its node attributes.
+    pub fn from_interaction(interaction: &ProteinInteraction, network: &InteractionNetwork) -> Self {
+        let attribute = |node: &str, key: &str| {
+            network
+                .node_attributes
+                .get(node)
+                .and_then(|attributes| attributes.get(key))
+                .cloned()
+        };
+        let id = |node: &str| {
+            let accession = attribute(node, "accession").unwrap_or_else(|| node.to_string());
+            let namespace = attribute(node, "id_namespace").unwrap_or_else(|| "uniprotkb".to_string());
+            format!("{}:{}", namespace, accession)
+        };
+        let alias = |node: &str| {
+            dash_if_empty(
+                attribute(node, "gene_name")
+                    .map(|gene| format!("uniprotkb:{}(gene name)", gene))
+                    .unwrap_or_default(),
+            )
+        };
+        let taxid = |node: &str| {
+            dash_if_empty(attribute(node, "taxid").map(|t| format!("taxid:{}", t)).unwrap_or_default())
+        };
+
+        let kind = InteractionType::from_label(&interaction.interaction_type);
+        let type_term = if interaction.interaction_type.contains("MI:") {
+            interaction.interaction_type.clone()
+        } else {
+            mi_term_for(&kind).to_string()
+        };
+
+        let columns = vec![
+            id(&interaction.source),
+            id(&interaction.target),
+            "-".to_string(),
+            "-".to_string(),
+            alias(&interaction.source),
+            alias(&interaction.target),
+            "-".to_string(),
+            "-".to_string(),
+            "-".to_string(),
+            taxid(&interaction.source),
+            taxid(&interaction.target),
+            type_term,
+            "-".to_string(),
+            "-".to_string(),
+            format!("intact-miscore:{}", interaction.confidence),
+        ];
+        Self { columns }
+    }
+
+    pub fn to_line(&self) -> String {
+        self.columns.join("\t")
+    }
+}
+
+impl MitabReader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_gene_names(mut self) -> Self {
+        self.prefer_gene_names = true;
+        self
+    }
+
+    pub fn read<P: AsRef<Path>>(&self, path: P) -> Result<MitabImport> {
+        let file = path.as_ref().display().to_string();
+        self.read_from(BufReader::new(File::open(path)?), &file)
+    }
+
+    pub fn read_from<R: BufRead>(&self, reader: R, file: &str) -> Result<MitabImport> {
+        let mut network = InteractionNetwork::new();
+        let mut records = Vec::new();
+        let mut row_errors = Vec::new();
+
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let record = match MitabRecord::parse(&line, file, index + 1) {
+                Ok(record) => record,
+                Err(e) => {
+                    row_errors.push(e);
+                    continue;
+                }
+            };
+            let confidence = record.confidence().unwrap_or(DEFAULT_CONFIDENCE);
+            if let Err(e) = MCL1Error::check_range("confidence", confidence, 0.0, 1.0) {
+                row_errors.push(MCL1Error::parse(file, index + 1, 15, e.to_string()));
+                continue;
+            }
+
+            for interactor_b in [false, true] {
+                let node = record.node_id(interactor_b, self.prefer_gene_names);
+                let (namespace, accession) = record.primary_id(interactor_b);
+                network.add_node_attribute(&node, "id_namespace", namespace);
+                network.add_node_attribute(&node, "accession", accession);
+                if let Some(gene) = record.gene_name(interactor_b) {
+                    network.add_node_attribute(&node, "gene_name", gene);
+                }
+                if let Some(taxid) = record.taxid(interactor_b) {
+                    network.add_node_attribute(&node, "taxid", taxid);
+                }
+            }
+
+            let interaction_type = record
+                .interaction_type_terms()
+                .first()
+                .map(|term| term.to_string())
+                .unwrap_or_else(|| InteractionType::Unknown.label().to_string());
+
+            network.add_interaction(ProteinInteraction::new(
+                &record.node_id(false, self.prefer_gene_names),
+                &record.node_id(true, self.prefer_gene_names),
+                &interaction_type,
+                confidence,
+            ));
+            records.push(record);
+        }
+
+        Ok(MitabImport {
+            network,
+            records,
+            row_errors,
+        })
+    }
+}
+
+/// Writes the network as MITAB. Each of `records` is written verbatim for
+/// at most one edge with the same interactors and interaction type, with
+/// its score rewritten if the edge's confidence has changed; other edges
+/// are synthesized. All lines share the widest record's column count,
+/// padded with `-` and capped at MITAB 2.7, and the header matches it.
+pub fn write_mitab<W: Write>(network: &InteractionNetwork, records: &[MitabRecord], mut writer: W) -> Result<()> {
+    let accession = |node: &str| -> String {
+        network
+            .node_attributes
+            .get(node)
+            .and_then(|attributes| attributes.get("accession"))
+            .cloned()
+            .unwrap_or_else(|| node.to_string())
+    };
+
+    let mut by_key: HashMap<(&str, &str, &str), Vec<usize>> = HashMap::new();
+    for (i, record) in records.iter().enumerate() {
+        let kind = record.interaction_type_terms().first().copied().unwrap_or("");
+        by_key
+            .entry((record.primary_id(false).1, record.primary_id(true).1, kind))
+            .or_default()
+            .push(i);
+    }
+
+    let mut used = vec![false; records.len()];
+    let mut lines = Vec::with_capacity(network.edge_count());
+    for edge in network.edges() {
+        let (source, target) = (accession(&edge.source), accession(&edge.target));
+        let candidates: Vec<usize> = by_key
+            .get(&(source.as_str(), target.as_str(), edge.interaction_type.as_str()))
+            .map_or(Vec::new(), |indices| indices.iter().copied().filter(|&i| !used[i]).collect());
+        let original = candidates
+            .iter()
+            .find(|&&i| records[i].confidence() == Some(edge.confidence))
+            .or_else(|| candidates.first());
+        let record = match original {
+            Some(&i) => {
+                used[i] = true;
+                if records[i].confidence() == Some(edge.confidence) {
+                    records[i].clone()
+                } else {
+                    records[i].with_confidence(edge.confidence)
+                }
+            }
+            None => MitabRecord::from_interaction(edge, network),
+        };
+        lines.push(record.columns);
+    }
+
+    let width = lines
+        .iter()
+        .map(Vec::len)
+        .max()
+        .unwrap_or(MITAB25_COLUMNS)
+        .clamp(MITAB25_COLUMNS, MITAB27_COLUMNS);
+    writeln!(writer, "#{}", MITAB_COLUMN_NAMES[..width].join("\t"))?;
+    for mut columns in lines {
+        columns.resize(width, "-".to_string());
+        writeln!(writer, "{}", columns.join("\t"))?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,9 @@
+pub mod biogrid;
+pub mod data_loader;
+pub mod fasta;
+pub mod math;
+pub mod mitab;
+pub mod model_builder;
+pub mod results_formatter;
+pub mod string_db;
+pub mod uniprot;
@@ -1,7 +1,8 @@
-rust
 use std::collections::HashMap;
 
-#[derive(Debug, Clone)]
+use crate::models::interaction::InteractionEdge;
+
+#[derive(Debug, Clone, Default)]
 pub struct ModelBuilder {
     pub parameters: HashMap<String, f64>,
     pub interactions: Vec<Interaction>,
@@ -15,6 +16,28 @@ pub struct Interaction {
     pub type_: String,
 }
 
+impl From<&Interaction> for InteractionEdge {
+    fn from(interaction: &Interaction) -> Self {
+        Self::from_label(
+            &interaction.source,
+            &interaction.target,
+            &interaction.type_,
+            interaction.weight,
+        )
+    }
+}
+
+impl From<&InteractionEdge> for Interaction {
+    fn from(edge: &InteractionEdge) -> Self {
+        Interaction {
+            source: edge.source.clone(),
+            target: edge.target.clone(),
+            weight: edge.confidence,
+            type_: edge.label.clone(),
+        }
+    }
+}
+
 impl ModelBuilder {
     pub fn new() -> Self {
         Self {
@@ -36,6 +59,13 @@ impl ModelBuilder {
         });
     }
 
+    pub fn add_edges<'a, I>(&mut self, edges: I)
+    where
+        I: IntoIterator<Item = &'a InteractionEdge>,
+    {
+        self.interactions.extend(edges.into_iter().map(Interaction::from));
+    }
+
     pub fn build_model(&self) -> Model {
         Model {
             parameters: self.parameters.clone(),
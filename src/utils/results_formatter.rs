@@ -1,4 +1,3 @@
-rust
 //! Utility module for formatting analysis results
 //!
 //! Provides functions to format and serialize MCL1 analysis results
@@ -7,6 +6,10 @@ rust
 use std::collections::HashMap;
 use serde_json::Value;
 
+use crate::analysis::centrality::{CentralityMeasure, CentralityTable};
+use crate::analysis::pathway_prediction::PathwayPredictionResult;
+use crate::mcl1_analyzer::AnalysisReport;
+
 /// Formats MCL1 interaction data into a human-readable string
 pub fn format_interactions(interactions: &[(String, String, f64)]) -> String {
     let mut output = String::new();
@@ -64,4 +67,96 @@ therapies with reduced cardiotoxicity.
 "#,
         total_interactions, active_pathways, predicted_drugs
     )
-}
\ No newline at end of file
+}
+
+/// Formats a pathway prediction result as one line per predicted pathway
+pub fn format_prediction_result(result: &PathwayPredictionResult) -> String {
+    let mut output = String::new();
+    output.push_str("Predicted Metabolic Pathways:\n");
+    output.push_str("=============================\n");
+
+    let mut pathways: Vec<_> = result.predicted_pathways.iter().collect();
+    pathways.sort_by(|a, b| b.mcl1_interaction_score.total_cmp(&a.mcl1_interaction_score));
+
+    for pathway in pathways {
+        output.push_str(&format!(
+            "{}\t{:.3}\t{}\n",
+            pathway.id,
+            pathway.mcl1_interaction_score,
+            pathway.associated_proteins.join(";")
+        ));
+    }
+
+    output.push_str(&format!(
+        "Confidence: {:.3}\nTimestamp: {}\n",
+        result.confidence_score, result.prediction_timestamp
+    ));
+    output
+}
+
+/// Formats a full analysis report in the style of the command-line output
+pub fn format_analysis_report(report: &AnalysisReport) -> String {
+    let mut output = String::new();
+    output.push_str("MCL1 Analysis Results:\n");
+    output.push_str(&format!(
+        "• Interactions: {} across {} proteins\n",
+        report.total_interactions, report.total_proteins
+    ));
+    output.push_str(&format!(
+        "• MCL1 Partners ({}): {}\n",
+        report.mcl1_partners.len(),
+        report.mcl1_partners.join(", ")
+    ));
+    output.push_str(&format!(
+        "• Metabolic Pathway Control: {:.1}%\n",
+        report.pathway_prediction.confidence_score * 100.0
+    ));
+
+    for pathway in &report.pathway_prediction.predicted_pathways {
+        output.push_str(&format!(
+            "  - {} ({:.1}%)\n",
+            pathway.id,
+            pathway.mcl1_interaction_score * 100.0
+        ));
+    }
+
+    output.push_str("• Cardiotoxicity Risk:\n");
+    if report.drug_targets.is_empty() {
+        output.push_str("  - No targets above threshold\n");
+    }
+    for target in &report.drug_targets {
+        output.push_str(&format!(
+            "  - {} ({:.0}%)\n",
+            target.target_protein,
+            target.cardiotoxicity_risk * 100.0
+        ));
+    }
+
+    output
+}
+
+/// Formats the top `limit` rows of a centrality table, ranked by `measure`
+pub fn format_centrality_table(table: &CentralityTable, measure: CentralityMeasure, limit: usize) -> String {
+    let mut output = String::new();
+    output.push_str(&format!("Centrality ranking by {}:\n", measure.label()));
+    output.push_str(&format!(
+        "{:>4}  {:<12} {:>6} {:>9} {:>11} {:>9} {:>11} {:>8}\n",
+        "rank", "node", "degree", "weighted", "betweenness", "closeness", "eigenvector", "pagerank"
+    ));
+
+    for (rank, row) in table.ranked(measure).into_iter().take(limit).enumerate() {
+        output.push_str(&format!(
+            "{:>4}  {:<12} {:>6} {:>9.3} {:>11.4} {:>9.4} {:>11.4} {:>8.4}\n",
+            rank + 1,
+            row.node,
+            row.degree,
+            row.weighted_degree,
+            row.betweenness,
+            row.closeness,
+            row.eigenvector,
+            row.pagerank
+        ));
+    }
+
+    output
+}
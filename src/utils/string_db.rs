@@ -0,0 +1,167 @@
+//! Loader for STRING `protein.links.detailed` flat files
+//!
+//! Each line scores a protein pair per evidence channel on a 0-1000 scale;
+//! the chosen channel becomes the edge confidence.
+
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use crate::analysis::interaction_network::{InteractionNetwork, ProteinInteraction};
+use crate::error::{MCL1Error, Result};
+
+/// Evidence channels of a STRING detailed links file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StringChannel {
+    Neighborhood,
+    Fusion,
+    Cooccurrence,
+    Coexpression,
+    Experimental,
+    Database,
+    Textmining,
+    Combined,
+}
+
+impl StringChannel {
+    /// Column name of the channel in the file header.
+    pub fn column(&self) -> &'static str {
+        match self {
+            StringChannel::Neighborhood => "neighborhood",
+            StringChannel::Fusion => "fusion",
+            StringChannel::Cooccurrence => "cooccurence",
+            StringChannel::Coexpression => "coexpression",
+            StringChannel::Experimental => "experimental",
+            StringChannel::Database => "database",
+            StringChannel::Textmining => "textmining",
+            StringChannel::Combined => "combined_score",
+        }
+    }
+}
+
+/// Builds an `InteractionNetwork` from a STRING detailed links file.
+#[derive(Debug, Clone)]
+pub struct StringLoader {
+    pub channel: StringChannel,
+    /// Keep only pairs where both proteins belong to this taxon.
+    pub taxon_id: Option<u32>,
+    /// Minimum channel score on STRING's 0-1000 scale.
+    pub min_score: u32,
+}
+
+impl Default for StringLoader {
+    fn default() -> Self {
+        Self::new(StringChannel::Combined)
+    }
+}
+
+/// Splits `9606.ENSP00000358022` into taxon and protein ID.
+fn split_string_id(id: &str) -> (Option<u32>, &str) {
+    match id.split_once('.') {
+        Some((taxon, protein)) => match taxon.parse() {
+            Ok(taxon) => (Some(taxon), protein),
+            Err(_) => (None, id),
+        },
+        None => (None, id),
+    }
+}
+
+impl StringLoader {
+    pub fn new(channel: StringChannel) -> Self {
+        Self {
+            channel,
+            taxon_id: None,
+            min_score: 1,
+        }
+    }
+
+    pub fn with_taxon(mut self, taxon_id: u32) -> Self {
+        self.taxon_id = Some(taxon_id);
+        self
+    }
+
+    pub fn with_min_score(mut self, min_score: u32) -> Self {
+        self.min_score = min_score;
+        self
+    }
+
+    pub fn load<P: AsRef<Path>>(&self, path: P) -> Result<InteractionNetwork> {
+        let file = path.as_ref().display().to_string();
+        self.load_from(BufReader::new(File::open(path)?), &file)
+    }
+
+    /// STRING lists every pair in both directions; only the first
+    /// occurrence of each unordered pair is kept.
+    pub fn load_from<R: BufRead>(&self, reader: R, file: &str) -> Result<InteractionNetwork> {
+        let mut lines = reader.lines();
+        let header = match lines.next() {
+            Some(line) => line?,
+            None => return Ok(InteractionNetwork::new()),
+        };
+        let columns: Vec<&str> = header.split_whitespace().collect();
+        let score_column = columns
+            .iter()
+            .position(|c| *c == self.channel.column())
+            .ok_or_else(|| MCL1Error::parse(file, 1, 0, format!("missing column '{}'", self.channel.column())))?;
+
+        let mut network = InteractionNetwork::new();
+        let mut seen = HashSet::new();
+
+        for (index, line) in lines.enumerate() {
+            let line = line?;
+            let line_number = index + 2;
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.is_empty() {
+                continue;
+            }
+            if fields.len() != columns.len() {
+                return Err(MCL1Error::parse(
+                    file,
+                    line_number,
+                    fields.len(),
+                    format!("expected {} columns, found {}", columns.len(), fields.len()),
+                ));
+            }
+
+            let (taxon_a, protein_a) = split_string_id(fields[0]);
+            let (taxon_b, protein_b) = split_string_id(fields[1]);
+            if let Some(taxon) = self.taxon_id {
+                if taxon_a != Some(taxon) || taxon_b != Some(taxon) {
+                    continue;
+                }
+            }
+
+            let score: u32 = fields[score_column].parse().map_err(|e| {
+                MCL1Error::parse(
+                    file,
+                    line_number,
+                    score_column + 1,
+                    format!("invalid score '{}': {}", fields[score_column], e),
+                )
+            })?;
+            if score < self.min_score {
+                continue;
+            }
+            let confidence = MCL1Error::check_range(self.channel.column(), score as f64 / 1000.0, 0.0, 1.0)?;
+
+            let pair = if protein_a <= protein_b {
+                (protein_a.to_string(), protein_b.to_string())
+            } else {
+                (protein_b.to_string(), protein_a.to_string())
+            };
+            if !seen.insert(pair) {
+                continue;
+            }
+
+            for (protein, taxon) in [(protein_a, taxon_a), (protein_b, taxon_b)] {
+                if let Some(taxon) = taxon {
+                    network.add_node_attribute(protein, "taxid", &taxon.to_string());
+                }
+            }
+            network.add_interaction(ProteinInteraction::new(protein_a, protein_b, "association", confidence));
+        }
+
+        Ok(network)
+    }
+}
@@ -0,0 +1,374 @@
+//! UniProtKB entry parser for the text (`.dat`) and XML formats
+//!
+//! Builds `Protein` records with their sequence, with one `Domain` per
+//! DOMAIN, REGION, MOTIF and TRANSMEM feature, and with a `PtmSite` per
+//! MOD_RES and single-residue CROSSLNK feature.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use quick_xml::events::{BytesStart, Event};
+
+use crate::error::{MCL1Error, Result};
+use crate::models::protein::{Domain, Protein};
+use crate::models::ptm::{ModificationType, PtmSite};
+
+/// Flat-file feature keys turned into domains by default.
+pub const DEFAULT_FEATURE_TYPES: &[&str] = &["DOMAIN", "REGION", "MOTIF", "TRANSMEM"];
+
+/// Maps XML feature types onto their flat-file keys.
+const XML_FEATURE_TYPES: &[(&str, &str)] = &[
+    ("domain", "DOMAIN"),
+    ("region of interest", "REGION"),
+    ("short sequence motif", "MOTIF"),
+    ("transmembrane region", "TRANSMEM"),
+    ("repeat", "REPEAT"),
+    ("zinc finger region", "ZN_FING"),
+    ("coiled-coil region", "COILED"),
+    ("compositionally biased region", "COMPBIAS"),
+    ("topological domain", "TOPO_DOM"),
+    ("modified residue", "MOD_RES"),
+    ("cross-link", "CROSSLNK"),
+    ("binding site", "BINDING"),
+    ("site", "SITE"),
+    ("sequence variant", "VARIANT"),
+    ("mutagenesis site", "MUTAGEN"),
+];
+
+#[derive(Debug, Clone)]
+pub struct UniProtParser {
+    pub feature_types: Vec<String>,
+}
+
+/// Entry fields collected while parsing, before they become a `Protein`.
+#[derive(Default)]
+struct EntryBuilder {
+    accession: Option<String>,
+    entry_name: Option<String>,
+    gene_name: Option<String>,
+    /// Set while inside the primary `<gene><name>` element.
+    in_primary_gene_name: bool,
+    mass: f64,
+    sequence: String,
+    features: Vec<Feature>,
+    /// Set while a `/note` qualifier continues on the following `FT` lines.
+    note_open: bool,
+}
+
+struct Feature {
+    key: String,
+    start: Option<usize>,
+    end: Option<usize>,
+    note: Option<String>,
+}
+
+impl Default for UniProtParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Parses a feature position, returning `None` for unknown (`?`) bounds.
+fn parse_position(value: &str) -> Option<usize> {
+    value.trim_start_matches(['<', '>']).parse().ok()
+}
+
+/// Builds a site from a `MOD_RES`/`CROSSLNK` feature; notes look like
+/// `Phosphoserine; by GSK3-beta`.
+fn ptm_site(feature: &Feature, sequence: &str) -> Option<PtmSite> {
+    if feature.key != "MOD_RES" && feature.key != "CROSSLNK" {
+        return None;
+    }
+    let position = feature.start.filter(|start| Some(*start) == feature.end)?;
+    let residue = sequence.chars().nth(position.checked_sub(1)?)?;
+    let note = feature.note.as_deref().unwrap_or("");
+    let (description, enzyme) = match note.split_once("; by ") {
+        Some((description, enzyme)) => (description, Some(enzyme.trim())),
+        None => (note, None),
+    };
+
+    let mut site = PtmSite::new(residue, position, ModificationType::from_label(description));
+    site.enzyme = enzyme.map(str::to_string);
+    Some(site)
+}
+
+/// Reads an attribute value from an XML start tag.
+fn attribute(element: &BytesStart, name: &[u8]) -> Option<String> {
+    element
+        .attributes()
+        .flatten()
+        .find(|a| a.key.as_ref() == name)
+        .and_then(|a| a.unescape_value().ok().map(|v| v.into_owned()))
+}
+
+impl EntryBuilder {
+    fn build(self, feature_types: &[String]) -> Option<Protein> {
+        let accession = self.accession?;
+        let name = self.gene_name.or(self.entry_name).unwrap_or_else(|| accession.clone());
+        let mut protein = Protein::from_sequence(accession, name, self.sequence);
+        if self.mass > 0.0 {
+            protein.molecular_weight = self.mass;
+        }
+
+        for feature in self.features {
+            // Residues come from the sequence itself, so sites need no checking.
+            if let Some(site) = ptm_site(&feature, &protein.sequence) {
+                protein.ptm_sites.push(site);
+            }
+            if !feature_types.contains(&feature.key) {
+                continue;
+            }
+            if let (Some(start), Some(end)) = (feature.start, feature.end) {
+                let name = feature.note.clone().unwrap_or_else(|| feature.key.clone());
+                protein.add_domain(Domain {
+                    id: format!("{}:{}-{}", feature.key, start, end),
+                    name,
+                    start,
+                    end,
+                    description: feature.key,
+                    score: None,
+                });
+            }
+        }
+
+        Some(protein)
+    }
+}
+
+impl UniProtParser {
+    pub fn new() -> Self {
+        Self {
+            feature_types: DEFAULT_FEATURE_TYPES.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    /// Replaces the flat-file feature keys that become domains.
+    pub fn with_feature_types(mut self, feature_types: &[&str]) -> Self {
+        self.feature_types = feature_types.iter().map(|t| t.to_string()).collect();
+        self
+    }
+
+    /// Loads a file, choosing the XML parser for `.xml` paths.
+    pub fn load<P: AsRef<Path>>(&self, path: P) -> Result<Vec<Protein>> {
+        let file = path.as_ref().display().to_string();
+        let reader = BufReader::new(File::open(&path)?);
+        let is_xml = path
+            .as_ref()
+            .extension()
+            .map(|e| e.eq_ignore_ascii_case("xml"))
+            .unwrap_or(false);
+
+        if is_xml {
+            self.parse_xml(reader, &file)
+        } else {
+            self.parse_text(reader, &file)
+        }
+    }
+
+    /// Parses UniProtKB text entries separated by `//` lines.
+    pub fn parse_text<R: BufRead>(&self, reader: R, file: &str) -> Result<Vec<Protein>> {
+        let mut proteins = Vec::new();
+        let mut entry = EntryBuilder::default();
+        let mut in_sequence = false;
+
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line_number = index + 1;
+            if line.starts_with("//") {
+                proteins.extend(std::mem::take(&mut entry).build(&self.feature_types));
+                in_sequence = false;
+                continue;
+            }
+            if in_sequence {
+                entry.sequence.extend(line.chars().filter(|c| c.is_ascii_alphabetic()));
+                continue;
+            }
+
+            let (code, content) = (line.get(..2).unwrap_or(""), line.get(5..).unwrap_or("").trim_end());
+            match code {
+                "ID" => entry.entry_name = content.split_whitespace().next().map(str::to_string),
+                "AC" if entry.accession.is_none() => {
+                    entry.accession = content.split(';').next().map(|a| a.trim().to_string());
+                }
+                "GN" if entry.gene_name.is_none() => {
+                    entry.gene_name = content
+                        .split(';')
+                        .find_map(|part| part.trim().strip_prefix("Name="))
+                        .map(|name| name.split(" {").next().unwrap_or(name).to_string());
+                }
+                "FT" => Self::parse_feature_line(&mut entry, &line, file, line_number)?,
+                "SQ" => {
+                    entry.mass = content
+                        .split(';')
+                        .find_map(|part| part.trim().strip_suffix(" MW"))
+                        .and_then(|mw| mw.trim().parse().ok())
+                        .unwrap_or(0.0);
+                    in_sequence = true;
+                }
+                _ => {}
+            }
+        }
+
+        proteins.extend(entry.build(&self.feature_types));
+        Ok(proteins)
+    }
+
+    /// Handles both the current `FT   MOTIF  209..223` layout with
+    /// `/note="..."` qualifiers, which may wrap onto continuation lines, and
+    /// the older column layout.
+    fn parse_feature_line(entry: &mut EntryBuilder, line: &str, file: &str, line_number: usize) -> Result<()> {
+        let body = line.get(5..).unwrap_or("");
+        if body.starts_with(' ') {
+            let text = body.trim();
+            let open = entry.note_open;
+            entry.note_open = false;
+            if let Some(feature) = entry.features.last_mut() {
+                if let Some(note) = text.strip_prefix("/note=\"") {
+                    feature.note = Some(note.trim_end_matches('"').to_string());
+                    entry.note_open = !note.ends_with('"');
+                } else if open && !text.starts_with('/') {
+                    let note = feature.note.get_or_insert_with(String::new);
+                    note.push(' ');
+                    note.push_str(text.trim_end_matches('"'));
+                    entry.note_open = !text.ends_with('"');
+                }
+            }
+            return Ok(());
+        }
+        entry.note_open = false;
+
+        let mut tokens = body.split_whitespace();
+        let key = tokens.next().unwrap_or("");
+        let location = tokens.next().unwrap_or("");
+        let (start, end, note) = match location.split_once("..") {
+            Some((start, end)) => (parse_position(start), parse_position(end), None),
+            None => {
+                let end = tokens.next();
+                let note: Vec<&str> = tokens.collect();
+                let note = Some(note.join(" ").trim_end_matches('.').to_string()).filter(|n| !n.is_empty());
+                match end {
+                    Some(end) => (parse_position(location), parse_position(end), note),
+                    None => (parse_position(location), parse_position(location), note),
+                }
+            }
+        };
+
+        if start.is_none() && end.is_none() && !location.contains('?') {
+            return Err(MCL1Error::parse(
+                file,
+                line_number,
+                6,
+                format!("invalid feature location '{}'", location),
+            ));
+        }
+
+        entry.features.push(Feature {
+            key: key.to_string(),
+            start,
+            end,
+            note,
+        });
+        Ok(())
+    }
+
+    /// Parses a UniProtKB XML document with one or more `<entry>` elements.
+    pub fn parse_xml<R: BufRead>(&self, reader: R, file: &str) -> Result<Vec<Protein>> {
+        let mut reader = quick_xml::Reader::from_reader(reader);
+        reader.config_mut().trim_text(true);
+
+        let mut proteins = Vec::new();
+        let mut entry = EntryBuilder::default();
+        let mut path: Vec<String> = Vec::new();
+        let mut buf = Vec::new();
+
+        loop {
+            let event = reader.read_event_into(&mut buf).map_err(|e| {
+                MCL1Error::parse(file, 0, 0, format!("at byte {}: {}", reader.error_position(), e))
+            })?;
+
+            match event {
+                Event::Start(element) => {
+                    let name = String::from_utf8_lossy(element.local_name().as_ref()).into_owned();
+                    self.start_xml_element(&mut entry, &path, &name, &element);
+                    path.push(name);
+                }
+                Event::Empty(element) => {
+                    let name = String::from_utf8_lossy(element.local_name().as_ref()).into_owned();
+                    self.start_xml_element(&mut entry, &path, &name, &element);
+                }
+                Event::Text(text) => {
+                    let text = text
+                        .unescape()
+                        .map_err(|e| MCL1Error::parse(file, 0, 0, e.to_string()))?;
+                    let parent = path.iter().rev().take(2).map(String::as_str).collect::<Vec<_>>();
+                    match parent.as_slice() {
+                        ["accession", "entry"] if entry.accession.is_none() => {
+                            entry.accession = Some(text.trim().to_string());
+                        }
+                        ["name", "entry"] => entry.entry_name = Some(text.trim().to_string()),
+                        ["name", "gene"] if entry.in_primary_gene_name => {
+                            entry.gene_name = Some(text.trim().to_string());
+                            entry.in_primary_gene_name = false;
+                        }
+                        ["sequence", "entry"] => {
+                            entry.sequence.extend(text.chars().filter(|c| c.is_ascii_alphabetic()));
+                        }
+                        _ => {}
+                    }
+                }
+                Event::End(_) => {
+                    let closed = path.pop();
+                    if closed.as_deref() == Some("entry") {
+                        proteins.extend(std::mem::take(&mut entry).build(&self.feature_types));
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        Ok(proteins)
+    }
+
+    fn start_xml_element(&self, entry: &mut EntryBuilder, path: &[String], name: &str, element: &BytesStart) {
+        let parent = path.last().map(String::as_str);
+        let in_feature = path.len() >= 2 && path[path.len() - 2] == "feature";
+        match (parent, name) {
+            (Some("gene"), "name") => {
+                entry.in_primary_gene_name =
+                    entry.gene_name.is_none() && attribute(element, b"type").as_deref() == Some("primary");
+            }
+            (Some("entry"), "feature") => {
+                let xml_type = attribute(element, b"type").unwrap_or_default();
+                let key = XML_FEATURE_TYPES
+                    .iter()
+                    .find(|(t, _)| *t == xml_type)
+                    .map(|(_, key)| key.to_string())
+                    .unwrap_or_else(|| xml_type.to_ascii_uppercase());
+                entry.features.push(Feature {
+                    key,
+                    start: None,
+                    end: None,
+                    note: attribute(element, b"description"),
+                });
+            }
+            (Some("location"), "begin" | "end" | "position") if in_feature => {
+                let position = attribute(element, b"position").and_then(|p| parse_position(&p));
+                if let Some(feature) = entry.features.last_mut() {
+                    if name != "end" {
+                        feature.start = position;
+                    }
+                    if name != "begin" {
+                        feature.end = position;
+                    }
+                }
+            }
+            (Some("entry"), "sequence") => {
+                entry.mass = attribute(element, b"mass").and_then(|m| m.parse().ok()).unwrap_or(0.0);
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use mcl1_regulator::analysis::alignment::{Aligner, AlignmentMode, MultipleAlignment, SubstitutionMatrix};
+use mcl1_regulator::analysis::motif_scan::MotifScanner;
+use mcl1_regulator::models::Protein;
+use mcl1_regulator::utils::fasta::read_fasta;
+
+const RESIDUES: &str = "ARNDCQEGHILKMFPSTWYV";
+
+fn protein(id: &str, sequence: &str) -> Protein {
+    Protein::from_sequence(id.to_string(), id.to_string(), sequence.to_string())
+}
+
+#[test]
+fn test_matrices_are_symmetric() {
+    for matrix in [SubstitutionMatrix::BLOSUM62, SubstitutionMatrix::PAM250] {
+        for a in RESIDUES.chars() {
+            for b in RESIDUES.chars() {
+                assert_eq!(matrix.score(a, b), matrix.score(b, a), "{} {}{}", matrix.name, a, b);
+            }
+        }
+    }
+    assert_eq!(SubstitutionMatrix::BLOSUM62.score('W', 'W'), 11);
+    assert_eq!(SubstitutionMatrix::PAM250.score('w', 'w'), 17);
+    assert_eq!(SubstitutionMatrix::BLOSUM62.score('X', 'A'), -1);
+}
+
+#[test]
+fn test_global_alignment_with_affine_gap() {
+    let aligner = Aligner::default().with_gap_penalty(10.0, 1.0);
+    let alignment = aligner.global("HEAGAWGHEE", "HEAGAWGHEE");
+    assert_eq!(alignment.score, 62.0);
+    assert_eq!(alignment.identity(), 1.0);
+
+    // One three-residue gap is cheaper than three separate ones.
+    let alignment = aligner.global("MKTWWWAYIAKQR", "MKTAYIAKQR");
+    assert_eq!(alignment.aligned_a, "MKTWWWAYIAKQR");
+    assert_eq!(alignment.aligned_b, "MKT---AYIAKQR");
+    assert_eq!(alignment.aligned_a.len(), alignment.aligned_b.len());
+}
+
+#[test]
+fn test_local_alignment_finds_bh3_in_mcl1() {
+    let proteins = read_fasta("data/bcl2_family.fasta").unwrap();
+    let alignment = Aligner::new(SubstitutionMatrix::PAM250).align_proteins(&proteins[0], &proteins[2], AlignmentMode::Local);
+    assert_eq!(alignment.start_a, 207);
+    assert_eq!(alignment.start_b, 0);
+    assert_eq!(alignment.aligned_a, proteins[2].sequence);
+    assert_eq!(alignment.identity(), 1.0);
+
+    assert!(Aligner::default().local("WWWW", "GGGG").is_empty());
+}
+
+#[test]
+fn test_progressive_alignment_keeps_input_order() {
+    let family = vec![
+        protein("A", "MKTAYIAKQRQISFVKSHFSRQ"),
+        protein("B", "MKTAYIAKQRQISFVKSHFSRQLEERLGLIEVQ"),
+        protein("C", "MKTAYIAKQRQISFVKAHFSRQ"),
+    ];
+    let msa = Aligner::default().progressive(&family);
+    assert_eq!(msa.ids, ["A", "B", "C"]);
+    assert!(msa.rows.iter().all(|row| row.len() == msa.width()));
+    for (row, protein) in msa.rows.iter().zip(&family) {
+        assert_eq!(row.replace('-', ""), protein.sequence);
+    }
+    assert!(Aligner::default().progressive(&[]).rows.is_empty());
+}
+
+#[test]
+fn test_conservation_maps_onto_domains() {
+    let mut mcl1 = read_fasta("data/bcl2_family.fasta").unwrap().remove(0);
+    MotifScanner::bh3().annotate(&mut mcl1);
+
+    // An ortholog mutated only in its N-terminal region.
+    let mut ortholog = mcl1.sequence.clone();
+    ortholog.replace_range(0..20, "AAAAAAAAAAAAAAAAAAAA");
+    let msa = Aligner::default().progressive(&[mcl1.clone(), protein("ortholog", &ortholog)]);
+
+    let conservation = msa.domain_conservation(&mcl1).unwrap();
+    assert_eq!(conservation.len(), 1);
+    assert_eq!(conservation[0].name, "BH3");
+    assert_eq!(conservation[0].conservation, 1.0);
+
+    let residues = msa.residue_conservation("Q07820").unwrap();
+    assert_eq!(residues.len(), mcl1.sequence.len());
+    assert!(residues[1] < 1.0);
+    assert!(msa.residue_conservation("missing").is_err());
+}
+
+#[test]
+fn test_conservation_counts_columns_in_characters() {
+    let msa = MultipleAlignment {
+        ids: vec!["a".to_string(), "b".to_string(), "c".to_string()],
+        rows: vec!["MKé-A".to_string(), "MKXLA".to_string(), "MKéLA".to_string()],
+    };
+    assert_eq!(msa.width(), 5);
+
+    let conservation = msa.conservation();
+    assert_eq!(conservation.len(), 5);
+    assert_eq!(conservation[0], 1.0);
+    assert!(conservation[2] > 0.0 && conservation[2] < 1.0);
+    assert!((conservation[3] - 2.0 / 3.0).abs() < 1e-12);
+
+    let residues = msa.residue_conservation("a").unwrap();
+    assert_eq!(residues, vec![1.0, 1.0, conservation[2], 1.0]);
+}
@@ -0,0 +1,109 @@
+use std::fs;
+use std::path::PathBuf;
+
+use mcl1_regulator::analysis::interaction_network::ProteinInteraction;
+use mcl1_regulator::utils::data_loader::TableLoader;
+use mcl1_regulator::MCL1Error;
+
+fn write_fixture(name: &str, contents: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(name);
+    fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn test_quoted_fields_and_header_order() {
+    let path = write_fixture(
+        "mcl1_loader_quoted.csv",
+        "confidence,interaction_type,source,target\n\
+         0.9,\"binding, BH3 groove\",MCL1,PMAIP1\n",
+    );
+
+    let load = TableLoader::new().load_interactions(&path).unwrap();
+    assert!(load.is_clean());
+    assert_eq!(load.records[0].interaction_type, "binding, BH3 groove");
+    assert_eq!(load.records[0].target, "PMAIP1");
+}
+
+#[test]
+fn test_tsv_with_column_mapping() {
+    let path = write_fixture(
+        "mcl1_loader_mapped.tsv",
+        "protein_a\tprotein_b\ttype\tscore\nMCL1\tBAK1\tinhibition\t0.95\n",
+    );
+
+    let load = TableLoader::tsv()
+        .map_column("protein_a", "source")
+        .map_column("protein_b", "target")
+        .map_column("type", "interaction_type")
+        .map_column("score", "confidence")
+        .load::<ProteinInteraction, _>(&path)
+        .unwrap();
+    assert_eq!(load.records.len(), 1);
+    assert_eq!(load.records[0].source, "MCL1");
+    assert_eq!(load.records[0].confidence, 0.95);
+}
+
+#[test]
+fn test_bad_rows_are_collected() {
+    let path = write_fixture(
+        "mcl1_loader_bad_rows.csv",
+        "source,target,interaction_type,confidence\n\
+         MCL1,BAK1,inhibition,0.95\n\
+         MCL1,BAX,inhibition,strong\n\
+         MCL1,BID\n\
+         MCL1,BBC3,binding,1.4\n\
+         MCL1,PMAIP1,binding,0.93\n",
+    );
+
+    let load = TableLoader::new().load_interactions(&path).unwrap();
+    assert_eq!(load.records.len(), 2);
+    assert_eq!(load.row_errors.len(), 3);
+
+    let locations: Vec<(usize, usize)> = load
+        .row_errors
+        .iter()
+        .map(|e| match e {
+            MCL1Error::Parse { line, column, .. } => (*line, *column),
+            other => panic!("unexpected error: {}", other),
+        })
+        .collect();
+    assert_eq!(locations, vec![(3, 4), (4, 0), (5, 4)]);
+}
+
+#[test]
+fn test_drug_target_and_pathway_tables() {
+    let targets = write_fixture(
+        "mcl1_loader_targets.csv",
+        "id,name,target_protein,mechanism_of_action,cardiotoxicity_risk,binding_affinity,selectivity_score\n\
+         S63845,S63845,MCL1,BH3 mimetic,0.2,0.19,0.95\n",
+    );
+    let load = TableLoader::new().load_drug_targets(&targets).unwrap();
+    assert!(load.records[0].is_safe_for_cardiac_use());
+    assert!(load.records[0].is_highly_selective());
+
+    let pathways = write_fixture(
+        "mcl1_loader_pathways.csv",
+        "id,name,genes,mcl1_interaction_score\n\
+         mtorc1,mTORC1 signaling,MTOR;RPTOR;RHEB,0.8\n",
+    );
+    let load = TableLoader::new().load_pathways(&pathways).unwrap();
+    assert_eq!(load.records[0].genes_involved, vec!["MTOR", "RPTOR", "RHEB"]);
+}
+
+#[test]
+fn test_malformed_variants_are_row_errors() {
+    let path = write_fixture(
+        "mcl1_loader_bad_variants.csv",
+        "gene,hgvs\n\
+         MCL1,p.R263A\n\
+         MCL1,p.Abc12G\n\
+         MCL1,p.Xyz12=\n\
+         MCL1,p.E173*\n",
+    );
+
+    let load = TableLoader::new().load_variants(&path).unwrap();
+    assert_eq!(load.records.len(), 2);
+    assert_eq!(load.row_errors.len(), 2);
+    assert!(matches!(load.row_errors[0], MCL1Error::Parse { line: 3, .. }));
+}
@@ -0,0 +1,101 @@
+use std::fs;
+use std::io::Write;
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use mcl1_regulator::models::protein::Protein;
+use mcl1_regulator::utils::fasta::{open_fasta, parse_header, read_fasta, FastaReader, FastaWriter};
+use mcl1_regulator::MCL1Error;
+
+#[test]
+fn test_parse_headers() {
+    assert_eq!(
+        parse_header("sp|Q07820|MCL1_HUMAN Induced myeloid leukemia OS=Homo sapiens GN=MCL1 PE=1"),
+        ("Q07820".to_string(), "MCL1".to_string())
+    );
+    assert_eq!(
+        parse_header("tr|A0A024R|A0A024R_HUMAN Uncharacterized"),
+        ("A0A024R".to_string(), "A0A024R_HUMAN".to_string())
+    );
+    assert_eq!(
+        parse_header("MCL1_BH3_peptide synthetic"),
+        ("MCL1_BH3_peptide".to_string(), "MCL1_BH3_peptide".to_string())
+    );
+}
+
+#[test]
+fn test_read_multi_fasta() {
+    let proteins = read_fasta("data/bcl2_family.fasta").unwrap();
+    assert_eq!(proteins.len(), 3);
+    assert_eq!(proteins[0].id, "Q07820");
+    assert_eq!(proteins[0].sequence.len(), 350);
+    assert_eq!(proteins[1].name, "PMAIP1");
+    assert_eq!(proteins[2].sequence, "KALETLRRVGDGVQRNHETAF");
+}
+
+#[test]
+fn test_read_gzip_fasta() {
+    let path = std::env::temp_dir().join("mcl1_family.fasta.gz");
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(&fs::read("data/bcl2_family.fasta").unwrap())
+        .unwrap();
+    fs::write(&path, encoder.finish().unwrap()).unwrap();
+
+    let names: Vec<String> = open_fasta(&path)
+        .unwrap()
+        .map(|p| p.unwrap().name)
+        .collect();
+    assert_eq!(names, vec!["MCL1", "PMAIP1", "MCL1_BH3_peptide"]);
+}
+
+#[test]
+fn test_invalid_residue_is_reported() {
+    let data = ">bad\nMFGLK\nMF1LK\n";
+    let err = FastaReader::new(data.as_bytes(), "bad.fasta")
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap_err();
+    assert!(matches!(err, MCL1Error::Parse { line: 3, column: 3, .. }));
+
+    let proteins: Vec<Protein> = FastaReader::new(data.as_bytes(), "bad.fasta")
+        .without_validation()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(proteins[0].sequence, "MFGLKMF1LK");
+}
+
+#[test]
+fn test_write_with_line_width() {
+    let proteins = read_fasta("data/bcl2_family.fasta").unwrap();
+    let mut output = Vec::new();
+    FastaWriter::new(50).write_all(&proteins, &mut output).unwrap();
+    let text = String::from_utf8(output).unwrap();
+
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines[0], ">Q07820 GN=MCL1");
+    assert_eq!(lines[1].len(), 50);
+    assert_eq!(lines[8], ">Q13794 GN=PMAIP1");
+
+    let reread: Vec<Protein> = FastaReader::new(text.as_bytes(), "written")
+        .collect::<Result<_, _>>()
+        .unwrap();
+    for (original, copy) in proteins.iter().zip(&reread) {
+        assert_eq!(original.id, copy.id);
+        assert_eq!(original.name, copy.name);
+        assert_eq!(original.sequence, copy.sequence);
+    }
+}
+
+#[test]
+fn test_write_wraps_non_ascii_by_residue() {
+    let data = ">odd\nMFéLKMFéLK\n";
+    let proteins: Vec<Protein> = FastaReader::new(data.as_bytes(), "odd.fasta")
+        .without_validation()
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    let mut output = Vec::new();
+    FastaWriter::new(3).write_all(&proteins, &mut output).unwrap();
+    let text = String::from_utf8(output).unwrap();
+    assert_eq!(text, ">odd\nMFé\nLKM\nFéL\nK\n");
+}
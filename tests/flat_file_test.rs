@@ -0,0 +1,71 @@
+use mcl1_regulator::utils::biogrid::BiogridLoader;
+use mcl1_regulator::utils::string_db::{StringChannel, StringLoader};
+use mcl1_regulator::MCL1Error;
+
+const MCL1: &str = "ENSP00000358022";
+
+#[test]
+fn test_string_combined_channel_and_taxon_filter() {
+    let network = StringLoader::new(StringChannel::Combined)
+        .with_taxon(9606)
+        .load("data/string_mcl1.links.detailed.txt")
+        .unwrap();
+
+    assert_eq!(network.edges().len(), 4);
+    assert_eq!(network.get_degree(MCL1), 3);
+    assert_eq!(network.edges()[0].confidence, 0.999);
+    assert_eq!(network.node_attributes[MCL1]["taxid"], "9606");
+    assert!(!network.contains_node("ENSMUSP00000029540"));
+}
+
+#[test]
+fn test_string_experimental_channel_drops_zero_scores() {
+    let network = StringLoader::new(StringChannel::Experimental)
+        .with_min_score(700)
+        .load("data/string_mcl1.links.detailed.txt")
+        .unwrap();
+
+    assert_eq!(network.edges().len(), 4);
+    assert_eq!(network.get_degree(MCL1), 2);
+    assert_eq!(network.edges()[1].confidence, 0.912);
+}
+
+#[test]
+fn test_string_rejects_missing_channel_column() {
+    let data = "protein1 protein2 combined_score\n9606.A 9606.B 900\n";
+    let err = StringLoader::new(StringChannel::Database)
+        .load_from(data.as_bytes(), "short.txt")
+        .unwrap_err();
+    assert!(matches!(err, MCL1Error::Parse { line: 1, .. }));
+}
+
+#[test]
+fn test_biogrid_throughput_confidence() {
+    let network = BiogridLoader::new()
+        .with_taxon(9606)
+        .load("data/biogrid_mcl1.tab3.txt")
+        .unwrap();
+
+    assert_eq!(network.edges().len(), 5);
+    assert_eq!(network.get_degree("MCL1"), 5);
+    assert_eq!(network.edges()[0].confidence, 0.9);
+    assert_eq!(network.edges()[1].confidence, 0.9);
+    assert_eq!(network.edges()[3].confidence, 0.6);
+    assert_eq!(network.edges()[2].interaction_type, "phosphorylation");
+    assert_eq!(network.node_attributes["BAK1"]["entrez_id"], "578");
+    assert!(!network.contains_node("Mcl1"));
+}
+
+#[test]
+fn test_biogrid_physical_only() {
+    let network = BiogridLoader::new()
+        .with_taxon(9606)
+        .with_throughput_confidence(0.95, 0.5)
+        .physical_only()
+        .load("data/biogrid_mcl1.tab3.txt")
+        .unwrap();
+
+    assert_eq!(network.edges().len(), 4);
+    assert!(network.edges().iter().all(|e| e.interaction_type != "genetic"));
+    assert_eq!(network.edges()[3].confidence, 0.5);
+}
@@ -3,7 +3,7 @@ use std::process::Command;
 #[test]
 fn integration_test_basic_analysis() {
     let output = Command::new("cargo")
-        .args(&["run", "--bin", "mcl1-analyzer", "data/sample_interactions.csv"])
+        .args(["run", "--bin", "mcl1-analyzer", "data/sample_interactions.csv"])
         .output()
         .expect("Failed to execute command");
 
@@ -13,7 +13,7 @@ fn integration_test_basic_analysis() {
 #[test]
 fn integration_test_pathway_prediction() {
     let output = Command::new("cargo")
-        .args(&[
+        .args([
             "run",
             "--bin",
             "pathway-predictor",
@@ -33,7 +33,7 @@ fn integration_test_pathway_prediction() {
 fn integration_test_interactive_mode() {
     // Test that the interactive analyzer binary compiles and runs without panicking
     let output = Command::new("cargo")
-        .args(&["run", "--bin", "interactive-analyzer"])
+        .args(["run", "--bin", "interactive-analyzer"])
         .output()
         .expect("Failed to execute command");
 
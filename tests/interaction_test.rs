@@ -0,0 +1,88 @@
+use mcl1_regulator::analysis::interaction_network::{InteractionNetwork, ProteinInteraction};
+use mcl1_regulator::models::protein::{Interaction, InteractionType, Protein};
+use mcl1_regulator::models::{Direction, InteractionEdge, Sign};
+use mcl1_regulator::utils::model_builder::{self, ModelBuilder};
+
+#[test]
+fn test_edge_kind_sets_direction_and_sign() {
+    let edge = InteractionEdge::from_label("MCL1", "BAK1", "Inhibition", 0.9);
+    assert_eq!(edge.kind, InteractionType::Inhibition);
+    assert_eq!(edge.direction, Direction::Directed);
+    assert_eq!(edge.sign, Sign::Negative);
+
+    let edge = InteractionEdge::from_label("MCL1", "PMAIP1", "complex", 0.8);
+    assert_eq!(edge.kind, InteractionType::Binding);
+    assert_eq!(edge.direction, Direction::Undirected);
+    assert_eq!(edge.sign, Sign::Unsigned);
+}
+
+#[test]
+fn test_network_interaction_round_trip() {
+    let original = ProteinInteraction::new("MCL1", "HUWE1", "ubiquitination", 0.88);
+    let edge = InteractionEdge::from(&original);
+    assert_eq!(edge.kind, InteractionType::Modification);
+
+    let back = ProteinInteraction::from(&edge);
+    assert_eq!(back.source, original.source);
+    assert_eq!(back.target, original.target);
+    assert_eq!(back.interaction_type, original.interaction_type);
+    assert_eq!(back.confidence, original.confidence);
+}
+
+#[test]
+fn test_model_builder_interaction_round_trip() {
+    let original = model_builder::Interaction {
+        source: "MTOR".to_string(),
+        target: "RPS6KB1".to_string(),
+        weight: 0.94,
+        type_: "custom-edge".to_string(),
+    };
+    let edge = InteractionEdge::from(&original);
+    assert_eq!(edge.kind, InteractionType::Unknown);
+
+    let back = model_builder::Interaction::from(&edge);
+    assert_eq!(back.type_, original.type_);
+    assert_eq!(back.weight, original.weight);
+}
+
+#[test]
+fn test_protein_interaction_round_trip() {
+    let original = Interaction {
+        partner_id: "P10415".to_string(),
+        partner_name: "BCL2".to_string(),
+        binding_affinity: 12.5,
+        interaction_type: InteractionType::Binding,
+        evidence: "PMID:12345".to_string(),
+    };
+    let edge = InteractionEdge::from_protein_interaction("Q07820", &original);
+    let back = edge.to_protein_interaction();
+
+    assert_eq!(back.partner_id, original.partner_id);
+    assert_eq!(back.partner_name, original.partner_name);
+    assert_eq!(back.binding_affinity, original.binding_affinity);
+    assert_eq!(back.interaction_type, original.interaction_type);
+    assert_eq!(back.evidence, original.evidence);
+}
+
+#[test]
+fn test_edges_feed_every_consumer() {
+    let edges = vec![
+        InteractionEdge::from_label("MCL1", "BAK1", "inhibition", 0.95),
+        InteractionEdge::from_label("PMAIP1", "MCL1", "binding", 0.93),
+        InteractionEdge::from_label("GSK3B", "MCL1", "phosphorylation", 0.85),
+    ];
+
+    let network = InteractionNetwork::from_edges(&edges);
+    assert_eq!(network.get_degree("MCL1"), 3);
+    assert_eq!(network.to_edges(), edges);
+
+    let mut protein = Protein::new("MCL1".to_string(), "MCL1".to_string(), String::new(), 0.0, 0.0);
+    protein.add_edges(&edges);
+    assert_eq!(protein.get_inhibitors().len(), 1);
+    assert_eq!(protein.get_binding_partners()[0].partner_id, "PMAIP1");
+    assert!(protein.get_interactions_by_type(&InteractionType::Modification).is_empty());
+
+    let mut builder = ModelBuilder::new();
+    builder.add_edges(&edges);
+    assert_eq!(builder.build_model().get_interactions_by_type("binding").len(), 1);
+}
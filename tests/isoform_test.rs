@@ -0,0 +1,118 @@
+use mcl1_regulator::analysis::{CardiotoxicityPredictor, InteractionNetwork};
+use mcl1_regulator::models::isoform::MCL1_ISOFORMS;
+use mcl1_regulator::models::{ExpressionTable, Gene, Interaction, Isoform, IsoformFunction, InteractionType, Protein};
+use mcl1_regulator::utils::data_loader::TableLoader;
+use mcl1_regulator::utils::fasta::read_fasta;
+
+/// MCL1 with truncated stand-ins for the short isoforms' sequences.
+fn mcl1_gene() -> Gene {
+    let long = read_fasta("data/bcl2_family.fasta").unwrap().remove(0);
+    let mut gene = Gene::new("MCL1");
+    for (name, function) in MCL1_ISOFORMS {
+        let length = match *name {
+            "MCL1-L" => long.sequence.len(),
+            "MCL1-S" => 271,
+            _ => 197,
+        };
+        let protein = Protein::from_sequence(name.to_string(), "MCL1".to_string(), long.sequence[..length].to_string());
+        gene.add_isoform(Isoform::new(name, *function, protein));
+    }
+
+    let long = gene.isoform_mut("MCL1-L").unwrap();
+    long.add_partner("BAK1", InteractionType::Inhibition, 0.95).unwrap();
+    long.add_partner("PMAIP1", InteractionType::Binding, 0.9).unwrap();
+    let short = gene.isoform_mut("MCL1-S").unwrap();
+    short.add_partner("BAK1", InteractionType::Inhibition, 0.4).unwrap();
+    short.add_partner("DNM1L", InteractionType::Binding, 0.9).unwrap();
+    gene
+}
+
+fn expression() -> ExpressionTable {
+    let load = TableLoader::new().load_expression("data/mcl1_isoform_expression.csv").unwrap();
+    assert!(load.is_clean());
+    ExpressionTable::from_records(load.records)
+}
+
+#[test]
+fn test_isoforms_keep_their_own_records() {
+    let mut gene = mcl1_gene();
+    assert_eq!(gene.isoforms.len(), 3);
+    assert_eq!(gene.isoform("MCL1-S").unwrap().function, IsoformFunction::ProApoptotic);
+    assert!(gene.isoform("MCL1-L").unwrap().protein.molecular_weight > gene.isoform("MCL1-ES").unwrap().protein.molecular_weight);
+    assert!(gene.isoform("MCL1-XL").is_err());
+    assert!(gene.isoform_mut("MCL1-ES").unwrap().add_partner("BAX", InteractionType::Binding, 1.5).is_err());
+}
+
+#[test]
+fn test_isoform_edges_come_from_protein_interactions() {
+    let mut gene = mcl1_gene();
+    let long = gene.isoform_mut("MCL1-L").unwrap();
+    assert_eq!(long.protein.interactions.len(), 2);
+    assert_eq!(long.protein.get_inhibitors()[0].partner_id, "BAK1");
+
+    long.protein.add_interaction(Interaction {
+        partner_id: "BAX".to_string(),
+        partner_name: "BAX".to_string(),
+        binding_affinity: 0.7,
+        interaction_type: InteractionType::Binding,
+        evidence: String::new(),
+    });
+    let edges = long.edges();
+    assert_eq!(edges.len(), 3);
+    assert!(edges.iter().all(|edge| edge.source == "MCL1-L"));
+    assert_eq!((edges[0].target.as_str(), edges[0].confidence), ("BAK1", 0.95));
+    assert_eq!((edges[2].target.as_str(), edges[2].confidence), ("BAX", 0.7));
+
+    let network = InteractionNetwork::new().for_isoform(&gene, "MCL1-L").unwrap();
+    assert_eq!(network.get_degree("MCL1"), 3);
+}
+
+#[test]
+fn test_expression_weights_normalize() {
+    let gene = mcl1_gene();
+    let weights = expression().weights(&gene).unwrap();
+    assert_eq!(weights, vec![0.75, 0.2, 0.05]);
+    assert!(ExpressionTable::new().weights(&gene).is_err());
+}
+
+#[test]
+fn test_network_per_isoform_and_weighted() {
+    let mut network = InteractionNetwork::new();
+    for interaction in TableLoader::new().load_interactions("data/sample_interactions.csv").unwrap().records {
+        network.add_interaction(interaction);
+    }
+    let gene = mcl1_gene();
+
+    let short = network.for_isoform(&gene, "MCL1-S").unwrap();
+    let mut partners: Vec<&String> = short.get_neighbors("MCL1");
+    partners.sort();
+    assert_eq!(partners, ["BAK1", "DNM1L"]);
+    assert_eq!(short.node_attributes["MCL1"]["isoform"], "MCL1-S");
+
+    let views = network.isoform_networks(&gene);
+    assert_eq!(views.len(), 3);
+    assert_eq!(views[2].1.get_degree("MCL1"), 0);
+    assert_eq!(views[0].1.edges().len() - views[0].1.get_degree("MCL1"), network.edges().len() - network.get_degree("MCL1"));
+
+    let weighted = network.isoform_weighted(&gene, &expression()).unwrap();
+    let bak1 = weighted.edges().iter().find(|e| e.source == "MCL1" && e.target == "BAK1").unwrap();
+    assert!((bak1.confidence - (0.75 * 0.95 + 0.2 * 0.4)).abs() < 1e-9);
+    assert_eq!(weighted.get_degree("MCL1"), 3);
+}
+
+#[test]
+fn test_cardiotoxicity_per_isoform_and_weighted() {
+    let gene = mcl1_gene();
+    let predictor = CardiotoxicityPredictor::default();
+
+    let per_isoform = predictor.predict_isoforms(&gene);
+    let names = |targets: &[mcl1_regulator::models::DrugTarget]| {
+        targets.iter().map(|t| t.target_protein.clone()).collect::<Vec<_>>()
+    };
+    assert_eq!(names(&per_isoform[0].targets), ["BAK1"]);
+    assert_eq!(names(&per_isoform[1].targets), ["DNM1L"]);
+    assert!(per_isoform[2].targets.is_empty());
+
+    let weighted = predictor.predict_weighted(&gene, &expression()).unwrap();
+    assert_eq!(names(&weighted), ["BAK1"]);
+}
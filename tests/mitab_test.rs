@@ -0,0 +1,132 @@
+use mcl1_regulator::analysis::interaction_network::ProteinInteraction;
+use mcl1_regulator::error::MCL1Error;
+use mcl1_regulator::models::protein::InteractionType;
+use mcl1_regulator::utils::mitab::{write_mitab, MitabReader, MITAB27_COLUMNS};
+
+#[test]
+fn test_read_mitab_into_network() {
+    let import = MitabReader::new().read("data/mcl1_intact.mitab").unwrap();
+    let network = &import.network;
+
+    assert_eq!(import.records.len(), 5);
+    assert_eq!(network.get_degree("Q07820"), 5);
+    assert_eq!(network.node_attributes["Q07820"]["gene_name"], "MCL1");
+    assert_eq!(network.node_attributes["Q16611"]["taxid"], "9606");
+
+    let record = &import.records[2];
+    assert_eq!(record.detection_methods(), vec!["psi-mi:\"MI:0114\"(x-ray crystallography)"]);
+    assert_eq!(record.publications(), vec!["pubmed:17389404", "imex:IM-12345"]);
+    assert_eq!(record.confidence(), Some(0.88));
+    assert_eq!(record.interaction_type(), InteractionType::Binding);
+
+    let phosphorylation = &network.edges()[4];
+    assert_eq!(phosphorylation.confidence, 0.66);
+    assert_eq!(
+        InteractionType::from_label(&phosphorylation.interaction_type),
+        InteractionType::Modification
+    );
+}
+
+#[test]
+fn test_gene_name_node_ids() {
+    let import = MitabReader::new().with_gene_names().read("data/mcl1_intact.mitab").unwrap();
+    let mut neighbors = import.network.get_neighbors("MCL1");
+    neighbors.sort();
+    assert_eq!(neighbors, vec!["BAK1", "BCL2L11", "GSK3B", "HUWE1", "PMAIP1"]);
+}
+
+#[test]
+fn test_subnetwork_round_trip_keeps_records() {
+    let reader = MitabReader::new().with_gene_names();
+    let import = reader.read("data/mcl1_intact.mitab").unwrap();
+    let subnetwork = import.network.get_subnetwork(&["BAK1"]);
+
+    let mut output = Vec::new();
+    write_mitab(&subnetwork, &import.records, &mut output).unwrap();
+    let text = String::from_utf8(output).unwrap();
+
+    let reread = reader.read_from(text.as_bytes(), "roundtrip").unwrap();
+    assert_eq!(reread.records.len(), 5);
+    for record in &reread.records {
+        assert!(import.records.contains(record));
+    }
+}
+
+#[test]
+fn test_synthesized_records_are_valid_mitab() {
+    let import = MitabReader::new().with_gene_names().read("data/mcl1_intact.mitab").unwrap();
+
+    let mut output = Vec::new();
+    write_mitab(&import.network, &[], &mut output).unwrap();
+    let text = String::from_utf8(output).unwrap();
+
+    let reread = MitabReader::new().with_gene_names().read_from(text.as_bytes(), "synthesized").unwrap();
+    assert_eq!(reread.records[0].columns.len(), 15);
+    assert_eq!(reread.records[0].primary_id(false), ("uniprotkb", "Q07820"));
+    assert_eq!(reread.network.edges(), import.network.edges());
+}
+
+#[test]
+fn test_mitab27_round_trip_keeps_width_and_every_record() {
+    let reader = MitabReader::new().with_gene_names();
+    let import = reader.read("data/mcl1_intact27.mitab").unwrap();
+    assert_eq!(import.records.len(), 3);
+    assert_eq!(import.network.edge_count(), 3);
+
+    let mut network = import.network.clone();
+    network.add_interaction(ProteinInteraction::new("MCL1", "HUWE1", "ubiquitination", 0.7));
+    let mut output = Vec::new();
+    write_mitab(&network, &import.records, &mut output).unwrap();
+    let text = String::from_utf8(output).unwrap();
+
+    assert!(text.lines().all(|line| line.split('\t').count() == MITAB27_COLUMNS));
+    assert!(text.starts_with("#ID(s) interactor A\t"));
+    assert!(text.lines().next().unwrap().ends_with("\tIdentification method participant B"));
+
+    let reread = reader.read_from(text.as_bytes(), "roundtrip27").unwrap();
+    assert_eq!(reread.records.len(), 4);
+    assert_eq!(&reread.records[..3], &import.records[..]);
+    assert_eq!(reread.records[3].columns[15], "-");
+}
+
+#[test]
+fn test_changed_confidence_rewrites_score() {
+    let reader = MitabReader::new().with_gene_names();
+    let import = reader.read("data/mcl1_intact27.mitab").unwrap();
+    let mut network = import.network.clone();
+    network.update_confidence(|edge| edge.confidence / 2.0);
+
+    let mut output = Vec::new();
+    write_mitab(&network, &import.records, &mut output).unwrap();
+    let reread = reader.read_from(output.as_slice(), "rescaled").unwrap();
+
+    let confidences: Vec<f64> = reread.network.edges().iter().map(|edge| edge.confidence).collect();
+    assert_eq!(confidences, vec![0.37, 0.31, 0.44]);
+    assert_eq!(reread.records[0].columns[14], "intact-miscore:0.37|author score:high");
+    for (before, after) in import.records.iter().zip(&reread.records) {
+        assert_eq!(before.publications(), after.publications());
+        assert_eq!(before.columns[15..], after.columns[15..]);
+    }
+}
+
+#[test]
+fn test_bad_rows_are_reported_not_fatal() {
+    let text = std::fs::read_to_string("data/mcl1_intact.mitab").unwrap();
+    let mut lines: Vec<String> = text.lines().map(str::to_string).collect();
+    lines[2] = lines[2].replace("intact-miscore:0.81", "intact-miscore:1.7");
+    lines[3] = lines[3].split('\t').take(10).collect::<Vec<_>>().join("\t");
+    let text = lines.join("\n");
+
+    let import = MitabReader::new().with_gene_names().read_from(text.as_bytes(), "bad.mitab").unwrap();
+    assert_eq!(import.records.len(), 3);
+    assert!(!import.network.contains_node("PMAIP1"));
+    let locations: Vec<(usize, usize)> = import
+        .row_errors
+        .iter()
+        .map(|e| match e {
+            MCL1Error::Parse { file, line, column, .. } if file == "bad.mitab" => (*line, *column),
+            other => panic!("unexpected error: {}", other),
+        })
+        .collect();
+    assert_eq!(locations, vec![(3, 15), (4, 10)]);
+}
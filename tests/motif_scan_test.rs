@@ -0,0 +1,71 @@
+use mcl1_regulator::analysis::motif_scan::{MotifScanner, Pssm, ScanMethod, BH3_SEEDS};
+use mcl1_regulator::models::{InteractionType, Protein};
+use mcl1_regulator::utils::fasta::read_fasta;
+
+#[test]
+fn test_pssm_rejects_unaligned_seeds() {
+    assert!(Pssm::from_alignment(&["LRRIGD", "LRRGD"], 0.5).is_err());
+    assert!(Pssm::from_alignment(&[], 0.5).is_err());
+
+    let pssm = Pssm::bh3();
+    assert_eq!(pssm.width(), BH3_SEEDS[0].len());
+    assert!(pssm.score("IAQELRRIGDEF").unwrap() > pssm.score("GGGGSGGGGSGG").unwrap());
+    assert!(pssm.score("IAQ").is_none());
+}
+
+#[test]
+fn test_finds_mcl1_bh3_as_domain() {
+    let mut mcl1 = read_fasta("data/bcl2_family.fasta").unwrap().remove(0);
+    let scanner = MotifScanner::bh3();
+    assert_eq!(scanner.annotate(&mut mcl1), 1);
+
+    let domain = mcl1.get_domain_by_name("BH3").unwrap();
+    assert_eq!((domain.start, domain.end), (209, 220));
+    assert_eq!(&mcl1.sequence[domain.start - 1..domain.end], "ALETLRRVGDGV");
+    assert!(domain.score.unwrap() > scanner.threshold);
+}
+
+#[test]
+fn test_consensus_fallback_catches_low_scoring_motif() {
+    let sequence = "PPPPPPPPLPPPGDPLPPPPPP";
+    let hits = MotifScanner::bh3().scan_sequence(sequence);
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].method, ScanMethod::Consensus);
+    assert_eq!(hits[0].start, 5);
+
+    assert!(MotifScanner::bh3().without_consensus().scan_sequence(sequence).is_empty());
+}
+
+#[test]
+fn test_ranks_proteome_and_proposes_bindings() {
+    let scanner = MotifScanner::bh3();
+    let candidates = scanner.scan_fasta("data/bh3_candidates.fasta").unwrap();
+    let names: Vec<&str> = candidates.iter().map(|c| c.protein.name.as_str()).collect();
+    assert_eq!(names, ["BCL2L11", "BBC3", "PMAIP1", "BAK1"]);
+
+    let mut candidates = candidates;
+    candidates.extend(scanner.rank(vec![Protein::from_sequence(
+        "Q07820".to_string(),
+        "MCL1".to_string(),
+        "KALETLRRVGDGVQRNHETAF".to_string(),
+    )]));
+    let edges = scanner.binding_hypotheses("MCL1", &candidates);
+    assert_eq!(edges.len(), 4);
+    assert!(edges.iter().all(|e| e.source == "MCL1" && e.kind == InteractionType::Binding));
+    assert!(edges.windows(2).all(|pair| pair[0].confidence >= pair[1].confidence));
+    assert!(edges.iter().all(|e| e.confidence > 0.0 && e.confidence <= 1.0));
+}
+
+#[test]
+fn test_non_ascii_sequence_uses_residue_positions() {
+    let hits = MotifScanner::bh3().scan_sequence("ééMSALETLRRVGDGVQRNHéé");
+    assert_eq!(hits.len(), 1);
+    assert_eq!((hits[0].start, hits[0].end), (5, 16));
+    assert_eq!(hits[0].sequence, "ALETLRRVGDGV");
+
+    let hits = MotifScanner::bh3().scan_sequence("PPPPPPPPLPPPGDPLééPPPP");
+    assert_eq!(hits[0].method, ScanMethod::Consensus);
+    assert_eq!(hits[0].start, 5);
+    assert_eq!(hits[0].sequence.chars().count(), Pssm::bh3().width());
+    assert!(Pssm::bh3().score("IAQELRRIGDEé").is_some());
+}
@@ -0,0 +1,611 @@
+use mcl1_regulator::analysis::centrality::{CentralityMeasure, PAGERANK_DAMPING};
+use mcl1_regulator::analysis::interaction_network::{InteractionNetwork, ProteinInteraction, SubnetworkFilter};
+use mcl1_regulator::analysis::propagation::{DEFAULT_DAMPING, DEFAULT_DIFFUSION_TIME, DEFAULT_RESTART};
+use mcl1_regulator::analysis::randomization::DEFAULT_SWAPS_PER_EDGE;
+use mcl1_regulator::analysis::{NetworkPath, PermutationTest, Perturbation, PerturbationAnalysis, Propagation};
+use mcl1_regulator::error::MCL1Error;
+use mcl1_regulator::models::{Direction, InteractionEdge, Sign};
+use mcl1_regulator::utils::results_formatter::format_centrality_table;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn interaction(source: &str, target: &str, confidence: f64) -> ProteinInteraction {
+    ProteinInteraction::new(source, target, "binding", confidence)
+}
+
+fn network() -> InteractionNetwork {
+    let mut network = InteractionNetwork::new();
+    for (source, target, confidence) in [
+        ("MCL1", "BAK1", 0.9),
+        ("MCL1", "BCL2L11", 0.95),
+        ("PMAIP1", "MCL1", 0.8),
+        ("BAK1", "BAX", 0.6),
+        ("MCL1", "MCL1", 0.5),
+        ("MTOR", "RPTOR", 0.99),
+    ] {
+        network.add_interaction(interaction(source, target, confidence));
+    }
+    network
+}
+
+#[test]
+fn test_adjacency_queries() {
+    let network = network();
+    assert_eq!((network.node_count(), network.edge_count()), (7, 6));
+    assert_eq!(network.nodes()[..3], ["MCL1", "BAK1", "BCL2L11"]);
+
+    assert_eq!(network.get_neighbors("MCL1"), vec!["BAK1", "BCL2L11", "PMAIP1", "MCL1"]);
+    assert_eq!(network.get_degree("MCL1"), 4);
+    assert_eq!(network.get_degree("BAX"), 1);
+    assert_eq!(network.get_degree("BCL2"), 0);
+    assert!(network.get_neighbors("BCL2").is_empty());
+
+    let bak1 = network.node_index("BAK1").unwrap();
+    let incident: Vec<(&str, f64)> = network
+        .incident(bak1)
+        .map(|(neighbor, edge)| (network.node_name(neighbor), edge.confidence))
+        .collect();
+    assert_eq!(incident, vec![("MCL1", 0.9), ("BAX", 0.6)]);
+}
+
+#[test]
+fn test_subnetwork_is_connected_component() {
+    let mut network = network();
+    network.add_node_attribute("BAX", "role", "effector");
+    network.add_node_attribute("BCL2", "role", "absent");
+
+    let component = network.get_subnetwork(&["BAX", "BCL2"]);
+    assert_eq!(component.edge_count(), 5);
+    assert_eq!(component.node_count(), 5);
+    assert!(!component.contains_node("MTOR"));
+    assert_eq!(component.node_attributes["BAX"]["role"], "effector");
+    assert_eq!(component.node_attributes["BCL2"]["role"], "absent");
+}
+
+#[test]
+fn test_update_confidence_keeps_topology() {
+    let mut network = network();
+    network.update_confidence(|edge| edge.confidence / 2.0);
+    assert_eq!(network.edges()[0].confidence, 0.45);
+    assert_eq!(network.get_neighbors("BAX"), vec!["BAK1"]);
+}
+
+fn names(network: &InteractionNetwork) -> Vec<&str> {
+    let mut names: Vec<&str> = network.nodes().iter().map(String::as_str).collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn test_neighborhood_depth_and_confidence() {
+    let mut network = network();
+    network.add_interaction(ProteinInteraction {
+        interaction_type: "phosphorylation".to_string(),
+        ..interaction("GSK3B", "MCL1", 0.7)
+    });
+    network.add_interaction(interaction("BAX", "VDAC2", 0.9));
+
+    let one_hop = network.neighborhood(&["MCL1"], &SubnetworkFilter::new().with_max_depth(1));
+    assert_eq!(names(&one_hop), vec!["BAK1", "BCL2L11", "GSK3B", "MCL1", "PMAIP1"]);
+    assert_eq!(one_hop.edge_count(), 5);
+
+    let two_hops = network.neighborhood(&["MCL1"], &SubnetworkFilter::new().with_max_depth(2));
+    assert!(two_hops.contains_node("BAX") && !two_hops.contains_node("VDAC2"));
+    // The BAK1-BAX edge lies between two selected nodes, so it is kept.
+    assert!(two_hops.get_neighbors("BAX").contains(&&"BAK1".to_string()));
+
+    let confident = network.neighborhood(&["MCL1"], &SubnetworkFilter::new().with_min_confidence(0.85));
+    assert_eq!(names(&confident), vec!["BAK1", "BCL2L11", "MCL1"]);
+
+    let modification = SubnetworkFilter::new().with_interaction_types(&["modification"]);
+    assert_eq!(names(&network.neighborhood(&["MCL1"], &modification)), vec!["GSK3B", "MCL1"]);
+    let unbounded = network.neighborhood(&["MCL1"], &SubnetworkFilter::new());
+    assert_eq!(unbounded.edge_count(), network.get_subnetwork(&["MCL1"]).edge_count());
+}
+
+#[test]
+fn test_neighborhood_max_nodes_keeps_strongest() {
+    let network = network();
+    let capped = network.neighborhood(&["MCL1", "BCL2"], &SubnetworkFilter::new().with_max_nodes(3));
+    assert_eq!(names(&capped), vec!["BAK1", "BCL2L11", "MCL1"]);
+    assert!(network.neighborhood(&["BCL2"], &SubnetworkFilter::new()).nodes().is_empty());
+}
+
+#[test]
+fn test_induced_subgraph() {
+    let mut network = network();
+    network.add_node_attribute("BAX", "role", "effector");
+
+    let induced = network.induced_subgraph(&["MCL1", "BAK1", "BAX", "RPTOR", "BCL2"]);
+    assert_eq!(names(&induced), vec!["BAK1", "BAX", "MCL1", "RPTOR"]);
+    assert_eq!(induced.edge_count(), 3);
+    assert_eq!(induced.get_degree("RPTOR"), 0);
+    assert_eq!(induced.node_attributes["BAX"]["role"], "effector");
+}
+
+fn unit_network(edges: &[(&str, &str)]) -> InteractionNetwork {
+    let mut network = InteractionNetwork::new();
+    for (source, target) in edges {
+        network.add_interaction(interaction(source, target, 1.0));
+    }
+    network
+}
+
+#[test]
+fn test_path_graph_centrality() {
+    let network = unit_network(&[("A", "B"), ("B", "C"), ("C", "D")]);
+
+    let betweenness = network.betweenness_centrality();
+    assert!((betweenness["B"] - 2.0 / 3.0).abs() < 1e-9);
+    assert_eq!(betweenness["A"], 0.0);
+
+    let closeness = network.closeness_centrality();
+    assert!((closeness["A"] - 0.5).abs() < 1e-9);
+    assert!((closeness["B"] - 0.75).abs() < 1e-9);
+
+    // Reference values from networkx.pagerank(nx.path_graph(4)).
+    let pagerank = network.pagerank(PAGERANK_DAMPING).unwrap();
+    assert!((pagerank["A"] - 0.17543839).abs() < 1e-6);
+    assert!((pagerank["B"] - 0.32456161).abs() < 1e-6);
+    assert!((pagerank.values().sum::<f64>() - 1.0).abs() < 1e-9);
+    assert!(network.pagerank(1.5).is_err());
+}
+
+#[test]
+fn test_star_and_weighted_centrality() {
+    let star = unit_network(&[("MCL1", "BAK1"), ("MCL1", "BAX"), ("BCL2L11", "MCL1")]);
+    let eigenvector = star.eigenvector_centrality();
+    assert!((eigenvector["MCL1"] - 0.5f64.sqrt()).abs() < 1e-6);
+    assert!((eigenvector["BAX"] - eigenvector["BAK1"]).abs() < 1e-9);
+    assert_eq!(star.betweenness_centrality()["MCL1"], 1.0);
+    assert_eq!(star.degree_centrality()["MCL1"], 3);
+
+    // A weak direct link loses to a confident two-step detour through C.
+    let mut network = InteractionNetwork::new();
+    network.add_interaction(interaction("A", "B", 0.2));
+    network.add_interaction(interaction("A", "C", 1.0));
+    network.add_interaction(interaction("C", "B", 1.0));
+    network.add_interaction(interaction("C", "B", 0.5));
+    assert_eq!(network.betweenness_centrality()["C"], 1.0);
+    assert!((network.weighted_degree_centrality()["C"] - 2.5).abs() < 1e-9);
+    assert_eq!(network.degree_centrality()["C"], 2);
+}
+
+#[test]
+fn test_annotated_centrality_table() {
+    let mut network = network();
+    let table = network.annotate_centrality();
+    assert_eq!(table.rows.len(), network.node_count());
+    assert_eq!(table.ranked(CentralityMeasure::Betweenness)[0].node, "MCL1");
+    assert_eq!(table.rank_of("MCL1", CentralityMeasure::Degree), Some(1));
+    let stored: f64 = network.node_attributes["BAK1"]["betweenness"].parse().unwrap();
+    assert_eq!(stored, table.row("BAK1").unwrap().betweenness);
+
+    let partners = network.partner_centrality("MCL1").unwrap();
+    let ranked: Vec<&str> = partners
+        .ranked(CentralityMeasure::Betweenness)
+        .iter()
+        .map(|row| row.node.as_str())
+        .collect();
+    assert_eq!(ranked, vec!["BAK1", "BCL2L11", "PMAIP1"]);
+    assert!(network.partner_centrality("BCL2").is_err());
+
+    let formatted = format_centrality_table(&partners, CentralityMeasure::Betweenness, 2);
+    assert_eq!(formatted.lines().count(), 4);
+    assert!(formatted.lines().nth(2).unwrap().contains("BAK1"));
+}
+
+fn mtor_routes() -> InteractionNetwork {
+    let mut network = InteractionNetwork::new();
+    for (source, target, confidence) in [
+        ("MCL1", "GSK3B", 0.9),
+        ("GSK3B", "TSC2", 0.9),
+        ("TSC2", "RHEB", 0.9),
+        ("RHEB", "MTOR", 0.95),
+        ("MCL1", "MTOR", 0.6),
+        ("MCL1", "BAK1", 0.95),
+        ("BAK1", "MTOR", 0.5),
+        ("BAK1", "MTOR", 0.2),
+        ("ULK1", "ATG13", 0.9),
+    ] {
+        network.add_interaction(interaction(source, target, confidence));
+    }
+    network
+}
+
+#[test]
+fn test_shortest_path_maximizes_confidence() {
+    let network = mtor_routes();
+    let path = network.shortest_path("MCL1", "MTOR").unwrap().unwrap();
+    assert_eq!(path.nodes, vec!["MCL1", "GSK3B", "TSC2", "RHEB", "MTOR"]);
+    assert_eq!(path.hops(), 4);
+    assert!((path.confidence - 0.9 * 0.9 * 0.9 * 0.95).abs() < 1e-12);
+    assert!((path.cost + path.confidence.ln()).abs() < 1e-12);
+
+    let lower_bound = -(0.95f64.ln());
+    let guided = network
+        .shortest_path_astar("MCL1", "MTOR", |node| if node == "MTOR" { 0.0 } else { lower_bound })
+        .unwrap()
+        .unwrap();
+    assert_eq!(guided.nodes, path.nodes);
+
+    assert!(network.shortest_path("MCL1", "ULK1").unwrap().is_none());
+    assert!(matches!(network.shortest_path("MCL1", "RPTOR"), Err(MCL1Error::UnknownProtein(_))));
+}
+
+#[test]
+fn test_astar_with_inconsistent_heuristic() {
+    // Link costs (-ln confidence): S-A 1, A-B 1, S-B 3, B-T 3, S-D 5, D-T 0.5.
+    let mut network = InteractionNetwork::new();
+    let costs: [(&str, &str, f64); 6] =
+        [("S", "A", 1.0), ("A", "B", 1.0), ("S", "B", 3.0), ("B", "T", 3.0), ("S", "D", 5.0), ("D", "T", 0.5)];
+    for (a, b, cost) in costs {
+        network.add_interaction(interaction(a, b, (-cost).exp()));
+    }
+    // Admissible (A is 4 from T) but inconsistent across A-B, so B is
+    // first reached the expensive way and must be expanded again.
+    let heuristic = |node: &str| if node == "A" { 3.0 } else { 0.0 };
+    let path = network.shortest_path_astar("S", "T", heuristic).unwrap().unwrap();
+    assert_eq!(path.nodes, vec!["S", "A", "B", "T"]);
+    assert!((path.cost - 5.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_k_shortest_and_all_paths() {
+    let network = mtor_routes();
+    let paths = network.k_shortest_paths("MCL1", "MTOR", 5).unwrap();
+    let routes: Vec<Vec<&str>> = paths.iter().map(|p| p.nodes.iter().map(String::as_str).collect()).collect();
+    assert_eq!(
+        routes,
+        vec![
+            vec!["MCL1", "GSK3B", "TSC2", "RHEB", "MTOR"],
+            vec!["MCL1", "MTOR"],
+            vec!["MCL1", "BAK1", "MTOR"],
+        ]
+    );
+    // Parallel BAK1-MTOR edges count once, at their best confidence.
+    assert!((paths[2].confidence - 0.475).abs() < 1e-12);
+    assert_eq!(network.k_shortest_paths("MCL1", "MTOR", 1).unwrap().len(), 1);
+
+    let all = network.all_paths("MCL1", "MTOR", 4).unwrap();
+    assert_eq!(all.len(), 3);
+    assert_eq!(all[0].nodes, paths[0].nodes);
+    let direct = network.all_paths("MCL1", "MTOR", 2).unwrap();
+    assert_eq!(direct.iter().map(NetworkPath::hops).collect::<Vec<_>>(), vec![1, 2]);
+    assert!(network.all_paths("MCL1", "ULK1", 10).unwrap().is_empty());
+}
+
+/// Apoptosis and mTOR cliques joined through MCL1, plus a pendant VDAC2.
+fn two_modules() -> InteractionNetwork {
+    let apoptosis = ["MCL1", "BAK1", "BAX", "BCL2L11", "PMAIP1"];
+    let metabolism = ["MTOR", "RPTOR", "RPS6KB1", "EIF4EBP1", "RHEB"];
+    let mut network = InteractionNetwork::new();
+    for module in [apoptosis, metabolism] {
+        for (i, a) in module.iter().enumerate() {
+            for b in &module[i + 1..] {
+                network.add_interaction(interaction(a, b, 0.9));
+            }
+        }
+    }
+    network.add_interaction(interaction("MCL1", "MTOR", 0.8));
+    network.add_interaction(interaction("MCL1", "RPTOR", 0.5));
+    network.add_interaction(interaction("BAK1", "VDAC2", 0.7));
+    network
+}
+
+#[test]
+fn test_modularity_clustering_separates_modules() {
+    let mut network = two_modules();
+    for communities in [network.louvain(1.0).unwrap(), network.leiden(1.0).unwrap(), network.label_propagation()] {
+        assert_eq!(communities.module_count(), 2, "{:?}", communities.method);
+        let apoptosis = communities.module_of("BAK1").unwrap();
+        assert_eq!(communities.module_of("MCL1"), Some(apoptosis));
+        assert_eq!(communities.module_of("VDAC2"), Some(apoptosis));
+        assert_ne!(communities.module_of("MTOR"), Some(apoptosis));
+        assert!(communities.modularity > 0.3);
+    }
+
+    let communities = network.louvain(1.0).unwrap();
+    assert_eq!(communities.modules()[0].len(), 6);
+    let bridge = network.participation_coefficient("MCL1", &communities).unwrap();
+    assert!(bridge > 0.3 && bridge < 0.5);
+    assert_eq!(network.participation_coefficient("BAX", &communities).unwrap(), 0.0);
+    assert_eq!(network.module_links("MCL1", &communities).unwrap().len(), 2);
+
+    network.annotate_modules(&communities);
+    assert_eq!(network.node_attributes["MCL1"]["louvain_module"], "0");
+    assert!(network.louvain(0.0).is_err());
+
+    // Very high resolution leaves every node on its own.
+    assert_eq!(network.louvain(100.0).unwrap().module_count(), network.node_count());
+}
+
+#[test]
+fn test_k_core_decomposition() {
+    let mut network = two_modules();
+    let cores = network.annotate_k_cores();
+    assert_eq!(cores["MCL1"], 4);
+    assert_eq!(cores["EIF4EBP1"], 4);
+    assert_eq!(cores["VDAC2"], 1);
+    assert_eq!(network.node_attributes["VDAC2"]["k_core"], "1");
+}
+
+fn signaling() -> InteractionNetwork {
+    let mut network = InteractionNetwork::new();
+    for (source, target, kind, confidence) in [
+        ("GSK3B", "MCL1", "inhibition", 0.85),
+        ("MCL1", "BAK1", "inhibition", 0.95),
+        ("MCL1", "BAX", "inhibition", 0.8),
+        ("BAK1", "CYCS", "activation", 0.9),
+        ("BAX", "CYCS", "activation", 0.85),
+        ("CYCS", "CASP9", "activation", 0.9),
+        ("MCL1", "PMAIP1", "binding", 0.93),
+    ] {
+        network.add_interaction(ProteinInteraction::new(source, target, kind, confidence));
+    }
+    network
+}
+
+#[test]
+fn test_directed_neighbors() {
+    let network = signaling();
+    assert_eq!(network.out_neighbors("BAK1"), vec!["CYCS"]);
+    assert_eq!(network.in_neighbors("BAK1"), vec!["MCL1"]);
+    assert_eq!(network.out_neighbors("MCL1"), vec!["BAK1", "BAX", "PMAIP1"]);
+    assert_eq!(network.in_neighbors("MCL1"), vec!["GSK3B", "PMAIP1"]);
+    assert_eq!((network.in_degree("CYCS"), network.out_degree("CYCS")), (2, 1));
+    assert_eq!(network.get_degree("CYCS"), 3);
+
+    let edge = &network.edges()[1];
+    assert_eq!((edge.direction(), edge.sign()), (Direction::Directed, Sign::Negative));
+    let binding = ProteinInteraction::new("MCL1", "MTOR", "binding", 0.7)
+        .with_direction(Direction::Directed)
+        .with_sign(Sign::Positive);
+    let round_trip = ProteinInteraction::from(&InteractionEdge::from(&binding));
+    assert_eq!(round_trip, binding);
+    assert_eq!(ProteinInteraction::from(&InteractionEdge::from(&network.edges()[1])).sign, None);
+}
+
+#[test]
+fn test_sign_consistent_paths() {
+    let network = signaling();
+    assert_eq!(Sign::Negative.then(Sign::Negative), Sign::Positive);
+    assert_eq!(Sign::Negative.then(Sign::Unsigned), Sign::Unsigned);
+
+    let paths = network.signed_paths("MCL1", "CASP9", 5).unwrap();
+    assert_eq!(paths.len(), 2);
+    assert_eq!(paths[0].nodes, vec!["MCL1", "BAK1", "CYCS", "CASP9"]);
+    assert!(paths.iter().all(|p| p.sign == Sign::Negative));
+    assert!((paths[0].confidence - 0.95 * 0.9 * 0.9).abs() < 1e-12);
+
+    // Inhibiting an inhibitor of apoptosis activates it.
+    let effect = network.net_effect("GSK3B", "CASP9").unwrap().unwrap();
+    assert_eq!(effect.sign, Sign::Positive);
+    assert_eq!(effect.nodes, vec!["GSK3B", "MCL1", "BAK1", "CYCS", "CASP9"]);
+    assert!(network.shortest_signed_path("MCL1", "CASP9", Sign::Positive).unwrap().is_none());
+    let negative = network.shortest_signed_path("MCL1", "CASP9", Sign::Negative).unwrap().unwrap();
+    assert_eq!(negative.nodes, paths[0].nodes);
+
+    // Edges are only followed forwards, and binding carries no sign.
+    assert!(network.net_effect("CASP9", "MCL1").unwrap().is_none());
+    assert!(network.net_effect("MCL1", "PMAIP1").unwrap().is_none());
+}
+
+#[test]
+fn test_signed_feedback_loops() {
+    let mut network = signaling();
+    // MCL1 -| GSK3B -| MCL1 is a positive loop; MCL1 -| BAK1 -> CYCS -> CASP9 -> MCL1 a negative one.
+    network.add_interaction(ProteinInteraction::new("MCL1", "GSK3B", "inhibition", 0.5));
+    assert!(network.shortest_signed_path("MCL1", "MCL1", Sign::Negative).unwrap().is_none());
+    network.add_interaction(ProteinInteraction::new("CASP9", "MCL1", "activation", 0.4));
+
+    let positive = network.shortest_signed_path("MCL1", "MCL1", Sign::Positive).unwrap().unwrap();
+    assert_eq!(positive.nodes, vec!["MCL1", "GSK3B", "MCL1"]);
+    assert!((positive.confidence - 0.5 * 0.85).abs() < 1e-12);
+    let negative = network.shortest_signed_path("MCL1", "MCL1", Sign::Negative).unwrap().unwrap();
+    assert_eq!(negative.nodes, vec!["MCL1", "BAK1", "CYCS", "CASP9", "MCL1"]);
+    assert_eq!(network.net_effect("MCL1", "MCL1").unwrap().unwrap().sign, Sign::Positive);
+    // Going round a negative loop twice is positive.
+    let twice = network.shortest_signed_path("BAK1", "BAK1", Sign::Positive).unwrap().unwrap();
+    assert_eq!(twice.nodes.len(), 9);
+    assert!(network.net_effect("PMAIP1", "PMAIP1").unwrap().is_none());
+}
+
+#[test]
+fn test_propagation_ranks_module_members() {
+    let network = two_modules();
+    let seeds = ["BAK1", "BAX"];
+
+    let walk = network.propagate(&seeds, Propagation::RandomWalkWithRestart { restart: DEFAULT_RESTART }).unwrap();
+    assert_eq!(walk.rows.len(), network.node_count());
+    assert!((walk.rows.iter().map(|r| r.score).sum::<f64>() - 1.0).abs() < 1e-8);
+    let top: Vec<&str> = walk.non_seeds().iter().take(3).map(|r| r.node.as_str()).collect();
+    assert!(top.iter().all(|node| ["MCL1", "BCL2L11", "PMAIP1", "VDAC2"].contains(node)), "{:?}", top);
+    assert!(walk.rank_of("MCL1").unwrap() < walk.rank_of("MTOR").unwrap());
+    assert!(walk.get("MTOR").unwrap().score > walk.get("EIF4EBP1").unwrap().score);
+
+    let heat = network.propagate(&seeds, Propagation::HeatDiffusion { time: DEFAULT_DIFFUSION_TIME }).unwrap();
+    assert!((heat.rows.iter().map(|r| r.score).sum::<f64>() - 1.0).abs() < 1e-8);
+    assert!(heat.get("MCL1").unwrap().score > heat.get("RPTOR").unwrap().score);
+    assert!(heat.get("RPTOR").unwrap().score > heat.get("RHEB").unwrap().score);
+
+    assert!(network.propagate(&["BCL2"], Propagation::HeatDiffusion { time: 1.0 }).is_err());
+    assert!(network.propagate(&[], Propagation::HeatDiffusion { time: 1.0 }).is_err());
+    assert!(network.propagate(&seeds, Propagation::RandomWalkWithRestart { restart: 1.5 }).is_err());
+}
+
+#[test]
+fn test_personalized_pagerank_follows_direction() {
+    let network = signaling();
+    let ppr = network.propagate(&["MCL1"], Propagation::PersonalizedPageRank { damping: DEFAULT_DAMPING }).unwrap();
+    assert_eq!(ppr.rows[0].node, "MCL1");
+    assert_eq!(ppr.get("GSK3B").unwrap().score, 0.0);
+    assert!(ppr.get("CASP9").unwrap().score > 0.0);
+}
+
+#[test]
+fn test_propagation_significance() {
+    let network = two_modules();
+    let method = Propagation::RandomWalkWithRestart { restart: DEFAULT_RESTART };
+    let tested = network.propagation_significance(&["BAK1", "BAX"], method, 200, 42).unwrap();
+    let again = network.propagation_significance(&["BAK1", "BAX"], method, 200, 42).unwrap();
+    assert_eq!(tested.rows, again.rows);
+
+    let p = |node: &str| tested.get(node).unwrap().p_value.unwrap();
+    assert!(tested.rows.iter().all(|r| r.p_value.is_some_and(|p| p > 0.0 && p <= 1.0)));
+    assert!(p("BCL2L11") < p("EIF4EBP1"));
+    assert!(p("EIF4EBP1") > 0.5);
+}
+
+#[test]
+fn test_significance_keeps_seed_set_size() {
+    // Both seeds share the only bin, so every random set must be both nodes.
+    let mut network = InteractionNetwork::new();
+    network.add_interaction(ProteinInteraction::new("BAK1", "BAX", "binding", 0.9));
+    let method = Propagation::RandomWalkWithRestart { restart: DEFAULT_RESTART };
+    let tested = network.propagation_significance(&["BAK1", "BAX"], method, 20, 1).unwrap();
+    assert!(tested.rows.iter().all(|r| r.p_value == Some(1.0)));
+}
+
+#[test]
+fn test_random_walk_returns_from_isolated_nodes() {
+    let mut network = two_modules();
+    network.add_interaction(ProteinInteraction::new("MCL1", "ORPHAN", "binding", 0.0));
+    let walk = network
+        .propagate(&["BAK1", "ORPHAN"], Propagation::RandomWalkWithRestart { restart: DEFAULT_RESTART })
+        .unwrap();
+    let total: f64 = walk.rows.iter().map(|r| r.score).sum();
+    assert!((total - 1.0).abs() < 1e-9);
+    assert!(walk.get("ORPHAN").unwrap().score > DEFAULT_RESTART / 2.0);
+}
+
+#[test]
+fn test_randomization_preserves_degrees() {
+    let network = two_modules();
+    let mut rng = StdRng::seed_from_u64(7);
+    let random = network.randomized(DEFAULT_SWAPS_PER_EDGE, &mut rng);
+    assert_eq!(random.edge_count(), network.edge_count());
+    assert_eq!(random.nodes(), network.nodes());
+    for node in network.nodes() {
+        assert_eq!(random.get_degree(node), network.get_degree(node), "{}", node);
+    }
+    let moved = random.edges().iter().zip(network.edges()).filter(|(a, b)| a != b).count();
+    assert!(moved > network.edge_count() / 2);
+    assert!(random.edges().iter().all(|e| e.source != e.target));
+
+    let signaling = signaling();
+    let random = signaling.randomized(DEFAULT_SWAPS_PER_EDGE, &mut rng);
+    for node in signaling.nodes() {
+        assert_eq!(random.in_degree(node), signaling.in_degree(node), "{}", node);
+        assert_eq!(random.out_degree(node), signaling.out_degree(node), "{}", node);
+    }
+}
+
+#[test]
+fn test_permutation_test_detects_modules() {
+    let network = two_modules();
+    let apoptosis = ["MCL1", "BAK1", "BAX", "BCL2L11", "PMAIP1"];
+    let within = |network: &InteractionNetwork| {
+        let count = network
+            .edges()
+            .iter()
+            .filter(|e| apoptosis.contains(&e.source.as_str()) && apoptosis.contains(&e.target.as_str()))
+            .count();
+        count as f64
+    };
+
+    let test = PermutationTest::new(200).with_seed(1);
+    let significance = test.run(&network, within).unwrap();
+    assert_eq!(significance.observed, 10.0);
+    assert_eq!(significance.null.len(), 200);
+    assert!(significance.z_score.unwrap() > 2.0);
+    assert!(significance.p_greater < 0.05);
+    assert!(significance.p_value() < 0.1);
+    assert_eq!(test.run(&network, within).unwrap(), significance);
+
+    let both = test.run_all(&network, |n| vec![within(n), n.edge_count() as f64]).unwrap();
+    assert_eq!(both[0], significance);
+    assert_eq!((both[1].z_score, both[1].p_greater), (None, 1.0));
+
+    let none = PermutationTest::new(0).run(&network, within);
+    assert!(matches!(none, Err(MCL1Error::InvalidParameter { ref name, .. }) if name == "permutations"));
+    let ragged = test.run_all(&network, |n| vec![0.0; usize::from(n.edges()[0].source == "MCL1")]);
+    assert!(matches!(ragged, Err(MCL1Error::InvalidParameter { ref name, .. }) if name == "statistic"));
+}
+
+fn module_analysis() -> PerturbationAnalysis {
+    PerturbationAnalysis::new().with_modules(
+        &["MCL1", "BAK1", "BAX", "BCL2L11", "PMAIP1"],
+        &["MTOR", "RPTOR", "RPS6KB1", "EIF4EBP1", "RHEB"],
+    )
+}
+
+#[test]
+fn test_node_and_edge_removal() {
+    let network = two_modules();
+    assert_eq!(network.connected_components().len(), 1);
+    assert_eq!(network.pairwise_connectivity(), 55);
+
+    let knocked_out = network.without_nodes(&["MCL1"]);
+    assert!(!knocked_out.contains_node("MCL1"));
+    assert_eq!(knocked_out.edge_count(), network.edge_count() - 6);
+    let components = knocked_out.connected_components();
+    assert_eq!(components.len(), 2);
+    assert_eq!(components[1], vec!["EIF4EBP1", "MTOR", "RHEB", "RPS6KB1", "RPTOR"]);
+
+    let cut = network.without_edges(&[("MTOR", "MCL1"), ("MCL1", "RPTOR")]);
+    assert_eq!(cut.node_count(), network.node_count());
+    assert_eq!(cut.connected_components().len(), 2);
+
+    let inhibited = network.inhibited("MCL1", 0.5).unwrap();
+    assert_eq!(inhibited.best_path_between(&["BAK1"], &["MTOR"]).unwrap().nodes, vec!["BAK1", "MCL1", "MTOR"]);
+    assert!((inhibited.best_path_between(&["MCL1"], &["MTOR"]).unwrap().confidence - 0.4).abs() < 1e-9);
+    assert!(network.inhibited("MCL1", 1.5).is_err());
+    assert!(matches!(network.inhibited("BCL2", 0.5), Err(MCL1Error::UnknownProtein(_))));
+}
+
+#[test]
+fn test_knockout_report_tracks_module_bridge() {
+    let network = two_modules();
+    let analysis = module_analysis();
+
+    let report = analysis.perturb(&network, &Perturbation::knock_out(&["MCL1"])).unwrap();
+    assert_eq!((report.components_before, report.components_after), (1, 2));
+    assert_eq!((report.largest_component_before, report.largest_component_after), (11, 5));
+    assert!((report.connectivity_loss - 35.0 / 55.0).abs() < 1e-9);
+    assert!(report.disconnects_modules());
+    assert!((report.path_confidence_loss() - 0.8).abs() < 1e-9);
+    assert_eq!(report.ranking_changes[0].node, "MCL1");
+    assert_eq!(report.ranking_changes[0].rank_before, 1);
+    assert_eq!(report.ranking_changes[0].shift(), None);
+
+    let report = analysis.perturb(&network, &Perturbation::remove_edges(&[("MCL1", "MTOR")])).unwrap();
+    assert_eq!(report.connectivity_loss, 0.0);
+    assert_eq!(report.module_path_after.unwrap().nodes, vec!["MCL1", "RPTOR"]);
+    assert!(report.ranking_changes.iter().all(|change| change.rank_after.is_some()));
+
+    let report = analysis.perturb(&network, &Perturbation::inhibit("MCL1", 0.1)).unwrap();
+    assert_eq!(report.connectivity_loss, 0.0);
+    assert!(report.path_confidence_loss() > 0.7);
+
+    let report = analysis.perturb(&network, &Perturbation::inhibit("MCL1", 0.0)).unwrap();
+    assert_eq!(report.components_after, 3);
+    assert!((report.connectivity_loss - 35.0 / 55.0).abs() < 1e-9);
+    assert!(report.disconnects_modules());
+
+    assert!(analysis.perturb(&network, &Perturbation::knock_out(&["BCL2"])).is_err());
+}
+
+#[test]
+fn test_knockout_screen_ranks_bridge_first() {
+    let network = two_modules();
+    let screen = module_analysis().knockout_screen(&network);
+    assert_eq!(screen.len(), network.node_count());
+    assert_eq!(screen[0].node, "MCL1");
+    assert_eq!(screen[0].path_confidence_after, None);
+    assert_eq!(screen[1].node, "MTOR");
+    assert!(screen.windows(2).all(|pair| pair[0].impact >= pair[1].impact));
+
+    let vdac2 = screen.iter().find(|row| row.node == "VDAC2").unwrap();
+    assert!(vdac2.connectivity_loss > 0.0 && vdac2.impact < screen[0].impact);
+}
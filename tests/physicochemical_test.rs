@@ -0,0 +1,67 @@
+use mcl1_regulator::analysis::physicochemical::{
+    average_mass, check_consistency, extinction_coefficient, find_inconsistent, gravy, instability_index,
+    isoelectric_point, monoisotopic_mass, net_charge, ConsistencyTolerance, PkaSet, SequenceProperties,
+};
+use mcl1_regulator::models::Protein;
+use mcl1_regulator::utils::fasta::read_fasta;
+
+fn mcl1() -> Protein {
+    read_fasta("data/bcl2_family.fasta")
+        .unwrap()
+        .into_iter()
+        .find(|p| p.name == "MCL1")
+        .unwrap()
+}
+
+#[test]
+fn test_masses_of_single_residue() {
+    assert!((average_mass("G") - 75.0672).abs() < 1e-3);
+    assert!((monoisotopic_mass("G") - 75.03203).abs() < 1e-4);
+    assert_eq!(average_mass(""), 0.0);
+}
+
+#[test]
+fn test_mcl1_matches_uniprot() {
+    let props = SequenceProperties::compute(&mcl1().sequence, &PkaSet::EMBOSS);
+    assert_eq!(props.length, 350);
+    assert!((props.average_mass - 37337.0).abs() < 1.0);
+}
+
+#[test]
+fn test_isoelectric_point_is_charge_neutral() {
+    let sequence = mcl1().sequence;
+    for pka in [PkaSet::EMBOSS, PkaSet::LEHNINGER, PkaSet::SOLOMON, PkaSet::DTASELECT, PkaSet::SILLERO] {
+        let pi = isoelectric_point(&sequence, &pka);
+        assert!(net_charge(&sequence, pi, &pka).abs() < 1e-2);
+    }
+    assert!(isoelectric_point("KKKKRR", &PkaSet::default()) > 10.0);
+    assert!(isoelectric_point("DDDDEE", &PkaSet::default()) < 4.0);
+}
+
+#[test]
+fn test_extinction_gravy_and_instability() {
+    let coefficient = extinction_coefficient("WYCC");
+    assert_eq!(coefficient.reduced, 6990.0);
+    assert_eq!(coefficient.cystines, 7115.0);
+
+    assert!((gravy("IVL") - 4.1667).abs() < 1e-3);
+    assert_eq!(instability_index("AA"), 5.0);
+    assert!((instability_index("AC") - 224.7).abs() < 1e-9);
+}
+
+#[test]
+fn test_consistency_flags_drift() {
+    let computed = Protein::from_sequence("P1".to_string(), "P1".to_string(), mcl1().sequence);
+    assert!(computed.check_consistency(&ConsistencyTolerance::default()).consistent);
+
+    let mut drifted = computed.clone();
+    drifted.molecular_weight = 40000.0;
+    let report = check_consistency(&drifted, &PkaSet::default(), &ConsistencyTolerance::default());
+    assert!(!report.consistent);
+    assert!(report.mass_drift.unwrap() > 0.05);
+
+    let undeclared = Protein::new("P2".to_string(), "P2".to_string(), "MKW".to_string(), 0.0, 0.0);
+    let flagged = find_inconsistent([&computed, &drifted, &undeclared], &PkaSet::default(), &ConsistencyTolerance::default());
+    assert_eq!(flagged.len(), 1);
+    assert_eq!(flagged[0].declared_mass, 40000.0);
+}
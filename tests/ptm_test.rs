@@ -0,0 +1,87 @@
+use mcl1_regulator::analysis::ptm_rules::{annotate_mcl1_sites, PtmEffect, PtmRule, PtmRuleEngine, SiteCondition};
+use mcl1_regulator::analysis::{InteractionNetwork, ProteinInteraction};
+use mcl1_regulator::error::MCL1Error;
+use mcl1_regulator::models::{ModificationType, Protein, PtmSite};
+use mcl1_regulator::utils::fasta::read_fasta;
+use mcl1_regulator::utils::uniprot::UniProtParser;
+
+fn mcl1() -> Protein {
+    let mut mcl1 = read_fasta("data/bcl2_family.fasta").unwrap().remove(0);
+    annotate_mcl1_sites(&mut mcl1).unwrap();
+    mcl1
+}
+
+#[test]
+fn test_sites_are_checked_against_sequence() {
+    let mut protein = mcl1();
+    assert_eq!(protein.ptm_sites.len(), 9);
+    assert_eq!(protein.ptm_site(159).unwrap().label(), "S159");
+
+    let err = protein
+        .add_ptm_site(PtmSite::new('T', 64, ModificationType::Phosphorylation))
+        .unwrap_err();
+    assert!(matches!(err, MCL1Error::InvalidSite { .. }));
+    assert!(protein.add_ptm_site(PtmSite::new('S', 999, ModificationType::Phosphorylation)).is_err());
+    assert!(protein.set_site_modified(2, true).is_err());
+}
+
+#[test]
+fn test_uniprot_mod_res_becomes_site() {
+    for path in ["data/Q07820.dat", "data/Q07820.xml"] {
+        let protein = UniProtParser::new().load(path).unwrap().remove(0);
+        let site = protein.ptm_site(64).unwrap();
+        assert_eq!((site.residue, site.modification), ('S', ModificationType::Phosphorylation));
+        assert!(!site.modified);
+    }
+    assert_eq!(ModificationType::from_label("N6-acetyllysine"), ModificationType::Acetylation);
+    assert_eq!(
+        ModificationType::from_label("Glycyl lysine isopeptide (Lys-Gly) (interchain with G-Cter in ubiquitin)"),
+        ModificationType::Ubiquitination
+    );
+}
+
+#[test]
+fn test_kinase_inhibitor_destabilizes_mcl1() {
+    let engine = PtmRuleEngine::mcl1();
+    let mut protein = mcl1();
+    assert_eq!(engine.evaluate(&protein).half_life, 40.0);
+
+    // ERK active: T163 phosphorylated, MCL1 stabilized.
+    protein.set_site_modified(163, true).unwrap();
+    assert_eq!(engine.evaluate(&protein).half_life, 80.0);
+
+    // ERK inhibitor removes the stabilizing phosphate.
+    assert_eq!(protein.set_enzyme_sites_modified("MAPK1", false), 1);
+    assert_eq!(engine.evaluate(&protein).half_life, 40.0);
+
+    // GSK3B then primes degradation, and ubiquitination follows.
+    protein.set_site_modified(159, true).unwrap();
+    protein.set_site_modified(136, true).unwrap();
+    let outcome = engine.evaluate(&protein);
+    assert_eq!(outcome.half_life, 5.0);
+    assert_eq!(outcome.fired_rules.len(), 2);
+}
+
+#[test]
+fn test_interaction_strength_rules_rescale_network() {
+    let mut network = InteractionNetwork::new();
+    for (target, confidence) in [("BAK1", 0.9), ("HUWE1", 0.8)] {
+        network.add_interaction(ProteinInteraction::new("MCL1", target, "binding", confidence));
+    }
+
+    let mut protein = mcl1();
+    protein.set_site_modified(64, true).unwrap();
+    let outcome = PtmRuleEngine::mcl1().apply_to_network(&protein, &mut network);
+    assert_eq!(outcome.interaction_factor("BAK1"), 1.2);
+    assert_eq!(network.edges()[0].confidence, 1.0);
+    assert_eq!(network.edges()[1].confidence, 0.8);
+
+    let engine = PtmRuleEngine::new(60.0)
+        .with_rule(
+            PtmRule::new("CDK1 phosphorylation weakens all binding", PtmEffect::InteractionStrength { partners: vec![], factor: 0.5 })
+                .when(SiteCondition::modified(ModificationType::Phosphorylation, None).with_enzyme("CDK1")),
+        )
+        .unwrap();
+    assert_eq!(engine.evaluate(&protein).interaction_factor("HUWE1"), 0.5);
+    assert!(PtmRuleEngine::new(60.0).with_rule(PtmRule::new("bad", PtmEffect::HalfLife(-1.0))).is_err());
+}
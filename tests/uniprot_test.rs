@@ -0,0 +1,75 @@
+use mcl1_regulator::models::protein::Protein;
+use mcl1_regulator::utils::uniprot::UniProtParser;
+use mcl1_regulator::MCL1Error;
+
+fn assert_mcl1(protein: &Protein) {
+    assert_eq!(protein.id, "Q07820");
+    assert_eq!(protein.name, "MCL1");
+    assert_eq!(protein.sequence.len(), 350);
+    assert!(protein.sequence.starts_with("MFGLKRNAVI"));
+    assert_eq!(protein.molecular_weight, 37337.0);
+
+    let bh3 = protein.get_domain_by_name("BH3").unwrap();
+    assert_eq!((bh3.start, bh3.end), (209, 223));
+    assert_eq!(bh3.description, "MOTIF");
+    for name in ["BH1", "BH2", "BH4", "PEST-like 1", "PEST-like 2"] {
+        assert!(protein.get_domain_by_name(name).is_some(), "missing {}", name);
+    }
+
+    let transmembrane = protein.get_domain_by_name("Helical").unwrap();
+    assert_eq!(transmembrane.description, "TRANSMEM");
+    assert_eq!((transmembrane.start, transmembrane.end), (328, 348));
+
+    assert!(protein.get_domain_by_name("Phosphoserine").is_none());
+}
+
+#[test]
+fn test_parse_uniprot_text_entry() {
+    let proteins = UniProtParser::new().load("data/Q07820.dat").unwrap();
+    assert_eq!(proteins.len(), 2);
+    assert_mcl1(&proteins[0]);
+
+    let noxa = &proteins[1];
+    assert_eq!(noxa.name, "PMAIP1");
+    let bh3 = noxa.get_domain_by_name("BH3").unwrap();
+    assert_eq!((bh3.start, bh3.end), (29, 43));
+}
+
+#[test]
+fn test_parse_uniprot_xml_entry() {
+    let proteins = UniProtParser::new().load("data/Q07820.xml").unwrap();
+    assert_eq!(proteins.len(), 1);
+    assert_mcl1(&proteins[0]);
+}
+
+#[test]
+fn test_custom_feature_types() {
+    let proteins = UniProtParser::new()
+        .with_feature_types(&["MOD_RES"])
+        .load("data/Q07820.xml")
+        .unwrap();
+    assert_eq!(proteins[0].domains.len(), 1);
+    assert_eq!(proteins[0].domains[0].start, 64);
+}
+
+#[test]
+fn test_malformed_feature_location() {
+    let text = "ID   TEST_HUMAN\nAC   P00000;\nFT   MOTIF           abc..def\n//\n";
+    let err = UniProtParser::new()
+        .parse_text(text.as_bytes(), "bad.dat")
+        .unwrap_err();
+    assert!(matches!(err, MCL1Error::Parse { line: 3, .. }));
+}
+
+#[test]
+fn test_wrapped_feature_note() {
+    let text = "ID   TEST_HUMAN\nAC   P00000;\n\
+FT   MOD_RES         3\n\
+FT                   /note=\"Phosphoserine; by GSK3-alpha and\n\
+FT                   GSK3-beta\"\n\
+FT                   /evidence=\"ECO:0000269\"\n\
+SQ   SEQUENCE   5 AA;  500 MW;  0000000000000000 CRC64;\n     MASKE\n//\n";
+    let protein = UniProtParser::new().parse_text(text.as_bytes(), "wrapped.dat").unwrap().remove(0);
+    assert_eq!(protein.ptm_sites.len(), 1);
+    assert_eq!(protein.ptm_sites[0].enzyme.as_deref(), Some("GSK3-alpha and GSK3-beta"));
+}
@@ -1,16 +1,22 @@
 use mcl1_regulator::{MCL1Analyzer, PathwayPredictor};
+use std::collections::HashMap;
 use std::fs;
 
 #[test]
 fn test_mcl1_analyzer_initialization() {
     let analyzer = MCL1Analyzer::new();
-    assert!(analyzer != None);
+    assert!(analyzer.cardiotoxicity_predictor.threshold > 0.0);
 }
 
 #[test]
 fn test_pathway_predictor_initialization() {
     let predictor = PathwayPredictor::new();
-    assert!(predictor != None);
+    let mut metabolic_data = HashMap::new();
+    metabolic_data.insert("mtorc1_signaling".to_string(), 0.9);
+    let result = predictor
+        .predict_pathways(&["MTOR".to_string()], &metabolic_data)
+        .unwrap();
+    assert_eq!(result.predicted_pathways.len(), 1);
 }
 
 #[test]
@@ -24,4 +30,100 @@ fn test_metabolic_pathway_prediction() {
     let analyzer = MCL1Analyzer::new();
     let result = analyzer.predict_metabolic_pathways("data/sample_interactions.csv");
     assert!(result.is_ok());
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_analysis_report_from_sample() {
+    let analyzer = MCL1Analyzer::new();
+    let report = analyzer
+        .analyze_interactions("data/sample_interactions.csv")
+        .unwrap();
+
+    assert_eq!(report.total_interactions, 20);
+    assert!(report.mcl1_partners.contains(&"BAK1".to_string()));
+    assert!(report
+        .pathway_prediction
+        .predicted_pathways
+        .iter()
+        .any(|p| p.id == "mtorc1_signaling"));
+    assert!(report.drug_targets.iter().any(|t| t.target_protein == "BAK1"));
+}
+
+#[test]
+fn test_interactive_session_commands() {
+    use mcl1_regulator::interactive::InteractiveAnalyzer;
+
+    let input = b"partners\nload data/sample_interactions.csv\ndegree MTOR\nquit\n";
+    let mut output = Vec::new();
+    InteractiveAnalyzer::new()
+        .run_with(&input[..], &mut output)
+        .unwrap();
+
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("No network loaded"));
+    assert!(output.contains("Loaded 20 interactions"));
+    assert!(output.lines().any(|line| line == "5"));
+}
+
+#[test]
+fn test_prediction_updates_pathway_collection() {
+    use mcl1_regulator::models::{MetabolicPathway, PathwayCollection};
+
+    let mut collection = PathwayCollection::new();
+    for id in ["mtorc1_signaling", "glycolysis"] {
+        collection.add_pathway(MetabolicPathway::new(
+            id.to_string(),
+            id.to_string(),
+            String::new(),
+            Vec::new(),
+            0.0,
+        ));
+    }
+
+    let result = MCL1Analyzer::new()
+        .predict_metabolic_pathways("data/sample_interactions.csv")
+        .unwrap();
+    result.apply_to(&mut collection);
+
+    let mtor = collection.get_pathway("mtorc1_signaling").unwrap();
+    assert!(mtor.is_active());
+    assert!(mtor.mcl1_interaction_score > 0.7);
+    assert!(mtor.associated_proteins.contains(&"MTOR".to_string()));
+    assert!(collection.get_pathway("glycolysis").unwrap().is_inactive());
+    assert_eq!(collection.active_pathways().count(), 1);
+}
+
+#[test]
+fn test_errors_instead_of_panics() {
+    use mcl1_regulator::MCL1Error;
+
+    let predictor = PathwayPredictor::with_parameters(HashMap::new());
+    let err = predictor.predict_pathways(&[], &HashMap::new()).unwrap_err();
+    assert!(matches!(err, MCL1Error::MissingParameter(ref name) if name == "mcl1_threshold"));
+
+    let mut metabolic_data = HashMap::new();
+    metabolic_data.insert("glycolysis".to_string(), 1.7);
+    let err = PathwayPredictor::new()
+        .predict_pathways(&[], &metabolic_data)
+        .unwrap_err();
+    assert!(matches!(err, MCL1Error::InvalidScore { .. }));
+
+    let err = MCL1Analyzer::new()
+        .analyze_interactions("data/does_not_exist.csv")
+        .unwrap_err();
+    assert!(matches!(err, MCL1Error::Io(_)));
+}
+
+#[test]
+fn test_parse_error_reports_location() {
+    use mcl1_regulator::MCL1Error;
+
+    let path = std::env::temp_dir().join("mcl1_bad_confidence.csv");
+    fs::write(&path, "source,target,interaction_type,confidence\nMCL1,BAK1,inhibition,high\n").unwrap();
+
+    let err = MCL1Analyzer::new().load_network(&path).unwrap_err();
+    match err {
+        MCL1Error::Parse { line, column, .. } => assert_eq!((line, column), (2, 4)),
+        other => panic!("unexpected error: {}", other),
+    }
+}
@@ -0,0 +1,210 @@
+use mcl1_regulator::analysis::ptm_rules::annotate_mcl1_sites;
+use mcl1_regulator::analysis::variant_effect::{ImpactEffect, VariantKind};
+use mcl1_regulator::analysis::{ProteinVariant, VariantAnnotator};
+use mcl1_regulator::error::MCL1Error;
+use mcl1_regulator::models::protein::{Interaction, InteractionType};
+use mcl1_regulator::models::Protein;
+use mcl1_regulator::utils::data_loader::TableLoader;
+use mcl1_regulator::utils::uniprot::UniProtParser;
+
+fn interaction(partner: &str, interaction_type: InteractionType, affinity: f64) -> Interaction {
+    Interaction {
+        partner_id: partner.to_string(),
+        partner_name: partner.to_string(),
+        binding_affinity: affinity,
+        interaction_type,
+        evidence: String::new(),
+    }
+}
+
+fn mcl1() -> Protein {
+    let mut mcl1 = UniProtParser::new().load("data/Q07820.dat").unwrap().remove(0);
+    mcl1.ptm_sites.clear();
+    annotate_mcl1_sites(&mut mcl1).unwrap();
+    mcl1.add_interaction(interaction("BAK1", InteractionType::Binding, 0.8));
+    mcl1.add_interaction(interaction("BCL2L11", InteractionType::Binding, 0.9));
+    mcl1.add_interaction(interaction("GSK3B", InteractionType::Modification, 0.6));
+    mcl1.add_interaction(interaction("DNM1L", InteractionType::Activation, 0.4));
+    mcl1
+}
+
+#[test]
+fn test_parse_notations() {
+    let variant = ProteinVariant::parse("p.R263A").unwrap();
+    assert_eq!(
+        (variant.kind, variant.start, variant.end),
+        (VariantKind::Missense, 263, 263)
+    );
+    assert_eq!(
+        (variant.reference.clone(), variant.alternate.as_str()),
+        (vec!['R'], "A")
+    );
+    assert_eq!(
+        ProteinVariant::parse("NP_068779.1:p.(Arg263Ala)").unwrap().alternate,
+        "A"
+    );
+
+    let kinds = [
+        ("p.S159*", VariantKind::Nonsense),
+        ("p.Ser159Ter", VariantKind::Nonsense),
+        ("p.S159=", VariantKind::Synonymous),
+        ("p.G217_D218del", VariantKind::Deletion),
+        ("p.R263dup", VariantKind::Duplication),
+        ("p.A209_L210insRR", VariantKind::Insertion),
+        ("p.K194_K197delinsA", VariantKind::DelIns),
+        ("p.Arg263GlyfsTer12", VariantKind::Frameshift),
+    ];
+    for (hgvs, kind) in kinds {
+        assert_eq!(ProteinVariant::parse(hgvs).unwrap().kind, kind, "{}", hgvs);
+    }
+
+    for bad in ["c.788G>A", "p.263A", "p.R0A", "p.A210_L209del", "p.A209_E212insR", "p.Abc12G", "p.Xyz12="] {
+        assert!(
+            matches!(ProteinVariant::parse(bad), Err(MCL1Error::Parse { .. })),
+            "{}",
+            bad
+        );
+    }
+}
+
+#[test]
+fn test_bh1_missense_disrupts_groove() {
+    let protein = mcl1();
+    let effect = VariantAnnotator::new()
+        .annotate(&protein, &ProteinVariant::parse("p.R263A").unwrap())
+        .unwrap();
+
+    assert!(effect.disrupts_binding_groove);
+    assert_eq!(
+        effect.hit_domains.iter().map(|d| d.name.as_str()).collect::<Vec<_>>(),
+        vec!["BH1"]
+    );
+    assert!(effect.hit_ptm_sites.is_empty());
+
+    let bak = effect.interactions.iter().find(|i| i.partner_id == "BAK1").unwrap();
+    assert_eq!(bak.effect, ImpactEffect::Reduced);
+    // BLOSUM62 scores R->A as -1, a non-conservative change.
+    assert!((bak.affinity_after.unwrap() - 0.4).abs() < 1e-9);
+    for partner in ["DNM1L", "GSK3B"] {
+        let impact = effect.interactions.iter().find(|i| i.partner_id == partner).unwrap();
+        assert_eq!((impact.effect, impact.affinity_after), (ImpactEffect::Unknown, None));
+    }
+
+    let synonymous = VariantAnnotator::new()
+        .annotate(&protein, &ProteinVariant::parse("p.R263=").unwrap())
+        .unwrap();
+    assert!(synonymous.interactions.is_empty() && !synonymous.disrupts_binding_groove);
+}
+
+#[test]
+fn test_reference_mismatch_is_rejected() {
+    let err = VariantAnnotator::new()
+        .annotate(&mcl1(), &ProteinVariant::parse("p.W263A").unwrap())
+        .unwrap_err();
+    assert!(matches!(err, MCL1Error::InvalidSite { ref site, .. } if site == "p.W263A"));
+}
+
+#[test]
+fn test_out_of_range_variant_is_rejected() {
+    let protein = mcl1();
+    let annotator = VariantAnnotator::new();
+    for hgvs in ["p.G500del", "p.K349_G400delinsA", "p.R351*"] {
+        let err = annotator.apply(&protein, &ProteinVariant::parse(hgvs).unwrap()).unwrap_err();
+        assert!(matches!(err, MCL1Error::InvalidSite { .. }), "{}", hgvs);
+    }
+
+    let mut variant = ProteinVariant::parse("p.G500del").unwrap();
+    variant.reference.clear();
+    assert!(matches!(annotator.apply(&protein, &variant), Err(MCL1Error::InvalidSite { .. })));
+}
+
+#[test]
+fn test_ptm_site_variants() {
+    let protein = mcl1();
+    let annotator = VariantAnnotator::new();
+
+    let effect = annotator
+        .annotate(&protein, &ProteinVariant::parse("p.Ser159Ala").unwrap())
+        .unwrap();
+    assert!(!effect.disrupts_binding_groove);
+    assert_eq!(effect.hit_ptm_sites[0].label(), "S159");
+    let gsk3b = effect.interactions.iter().find(|i| i.partner_id == "GSK3B").unwrap();
+    assert_eq!((gsk3b.effect, gsk3b.affinity_after), (ImpactEffect::Reduced, Some(0.0)));
+
+    let effect = annotator
+        .annotate(&protein, &ProteinVariant::parse("p.S159D").unwrap())
+        .unwrap();
+    let gsk3b = effect.interactions.iter().find(|i| i.partner_id == "GSK3B").unwrap();
+    assert_eq!(gsk3b.effect, ImpactEffect::Unknown);
+
+    let (mutated, _) = annotator
+        .apply(&protein, &ProteinVariant::parse("p.S159A").unwrap())
+        .unwrap();
+    assert!(mutated.ptm_site(159).is_none());
+    assert_eq!(
+        mutated
+            .interactions
+            .iter()
+            .find(|i| i.partner_id == "GSK3B")
+            .unwrap()
+            .binding_affinity,
+        0.0
+    );
+}
+
+#[test]
+fn test_apply_deletion_shifts_features() {
+    let protein = mcl1();
+    let (mutated, effect) = VariantAnnotator::new()
+        .apply(&protein, &ProteinVariant::parse("p.G217_D218del").unwrap())
+        .unwrap();
+
+    assert!(effect.disrupts_binding_groove);
+    assert_eq!(mutated.sequence.len(), protein.sequence.len() - 2);
+    assert!(mutated.molecular_weight < protein.molecular_weight);
+    let bh1 = mutated.get_domain_by_name("BH1").unwrap();
+    assert_eq!((bh1.start, bh1.end), (250, 269));
+    let bh3 = mutated.get_domain_by_name("BH3").unwrap();
+    assert_eq!((bh3.start, bh3.end), (209, 221));
+    assert!(effect
+        .interactions
+        .iter()
+        .all(|i| i.interaction_type != InteractionType::Binding || i.affinity_after == Some(0.0)));
+}
+
+#[test]
+fn test_truncation_drops_downstream_domains() {
+    let protein = mcl1();
+    let (mutated, effect) = VariantAnnotator::new()
+        .apply(&protein, &ProteinVariant::parse("p.E173*").unwrap())
+        .unwrap();
+
+    assert_eq!(mutated.sequence.len(), 172);
+    assert!(effect.disrupts_binding_groove);
+    assert!(mutated.get_domain_by_name("BH1").is_none());
+    assert!(mutated.domains.iter().all(|d| d.end <= 172));
+    assert!(mutated.ptm_sites.iter().all(|s| s.position <= 172));
+}
+
+#[test]
+fn test_load_variants() {
+    let load = TableLoader::new().load_variants("data/tumor_variants.csv").unwrap();
+    assert_eq!(load.records.len(), 7);
+    assert_eq!(load.row_errors.len(), 1);
+
+    let protein = mcl1();
+    let annotator = VariantAnnotator::new();
+    let disrupting: Vec<&str> = load
+        .records
+        .iter()
+        .filter(|record| record.gene == "MCL1")
+        .filter(|record| {
+            annotator
+                .annotate(&protein, &record.variant().unwrap())
+                .unwrap()
+                .disrupts_binding_groove
+        })
+        .map(|record| record.hgvs.as_str())
+        .collect();
+    assert_eq!(disrupting, vec!["p.R263A", "p.G217_D218del", "p.E173*", "p.R263Q"]);
+}